@@ -0,0 +1,62 @@
+//! Compares building each `SortedSet<T>` backend from the same keys: a `TreeNode<T>`, which
+//! recomputes subtree heights on every rebalance, against an `AvlTree<T>`, a `RedBlackTree<T>` and
+//! a `Treap<T>`, which keep their balancing information in the nodes. Run with `cargo bench`.
+
+use links::{
+    avl::AvlTree, rbtree::RedBlackTree, sorted_set::SortedSet, treap::Treap, tree::TreeNode,
+};
+use std::time::{Duration, Instant};
+
+/// Deterministic pseudo-random keys, so every run inserts the same sequence
+fn keys(n: usize) -> Vec<u64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect()
+}
+
+fn time<F: FnOnce()>(f: F) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+/// Times inserting `keys` one at a time into an empty `S`
+fn build<S: SortedSet<u64>>(keys: &[u64]) -> Duration {
+    time(|| {
+        let mut set = S::new();
+        for &key in keys {
+            set.insert(key);
+        }
+    })
+}
+
+fn main() {
+    println!(
+        "{:>9} {:>14} {:>14} {:>14} {:>14}",
+        "keys", "TreeNode", "AvlTree", "RedBlackTree", "Treap"
+    );
+    for n in [1_000, 2_000, 4_000, 8_000, 100_000, 1_000_000] {
+        let keys = keys(n);
+        // Each insertion into a `TreeNode<T>` takes time linear in its size, so it only gets the
+        // small inputs
+        let uncached = if n <= 8_000 {
+            format!("{:.2?}", build::<TreeNode<u64>>(&keys))
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:>9} {:>14} {:>14.2?} {:>14.2?} {:>14.2?}",
+            n,
+            uncached,
+            build::<AvlTree<u64>>(&keys),
+            build::<RedBlackTree<u64>>(&keys),
+            build::<Treap<u64>>(&keys)
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    borrow::Borrow,
+    ops::{Add, Bound, RangeBounds},
+};
+
+use crate::{
+    avl_core::Avl,
+    range::{after_start, before_end},
+};
+
+/// A summary of a run of values that can be computed for single values and combined, such as
+/// their count, sum or minimum.
+///
+/// Summaries form a monoid: `combine` must be associative and `identity` must leave any summary
+/// unchanged. `combine` need not be commutative; the left argument always summarizes smaller
+/// values than the right one.
+pub trait Augment<T> {
+    type Summary: Clone;
+
+    /// Returns the summary of no values
+    fn identity() -> Self::Summary;
+
+    /// Returns the summary of the single value `value`
+    fn summarize(value: &T) -> Self::Summary;
+
+    /// Returns the summary of the values summarized by `left` followed by those summarized by
+    /// `right`
+    fn combine(left: &Self::Summary, right: &Self::Summary) -> Self::Summary;
+}
+
+/// Counts the values
+pub struct Count;
+
+impl<T> Augment<T> for Count {
+    type Summary = usize;
+
+    fn identity() -> usize {
+        0
+    }
+
+    fn summarize(_: &T) -> usize {
+        1
+    }
+
+    fn combine(left: &usize, right: &usize) -> usize {
+        left + right
+    }
+}
+
+/// Adds up the values, starting from `T::default()`
+pub struct Sum;
+
+impl<T: Copy + Default + Add<Output = T>> Augment<T> for Sum {
+    type Summary = T;
+
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn summarize(value: &T) -> T {
+        *value
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left + *right
+    }
+}
+
+/// Computes the summaries `A` and `B` side by side
+impl<T, A: Augment<T>, B: Augment<T>> Augment<T> for (A, B) {
+    type Summary = (A::Summary, B::Summary);
+
+    fn identity() -> Self::Summary {
+        (A::identity(), B::identity())
+    }
+
+    fn summarize(value: &T) -> Self::Summary {
+        (A::summarize(value), B::summarize(value))
+    }
+
+    fn combine(left: &Self::Summary, right: &Self::Summary) -> Self::Summary {
+        (A::combine(&left.0, &right.0), B::combine(&left.1, &right.1))
+    }
+}
+
+/// An AVL tree that caches, in every node, the summary `A` of the values in its subtree.
+///
+/// The summaries are kept up to date through insertions, removals and rotations, so the summary
+/// of any range of values can be read off O(log n) nodes; with a suitable `A`, this makes the
+/// tree an ordered segment tree.
+pub struct AugmentedTree<T: Ord, A: Augment<T>>(Avl<T, A>);
+
+impl<T: Ord, A: Augment<T>> AugmentedTree<T, A> {
+    /// Creates a new empty tree
+    pub fn new() -> Self {
+        AugmentedTree(Avl::new())
+    }
+
+    /// Returns the height of the tree
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Returns `true` if the tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the summary of all values in the tree in O(1)
+    pub fn summary(&self) -> A::Summary {
+        self.0.summary()
+    }
+
+    /// Inserts `value` into the tree. If the value already exists in the tree, the function does
+    /// nothing.
+    ///
+    /// After insertion, the tree is rebalanced if necessary
+    pub fn insert(&mut self, value: T) {
+        let _ = self.0.insert_by(value, &T::cmp);
+    }
+
+    /// Removes `value` from the tree and returns it, or `None` if the value is not in the tree.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        self.0.remove_by(&|entry| value.cmp(entry))
+    }
+
+    /// Returns the summary of the values of the tree that lie in `range`, in O(log n)
+    pub fn fold_range<Q, R>(&self, range: R) -> A::Summary
+    where
+        Q: Ord + ?Sized,
+        T: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        let mut tree = &self.0;
+        // Find the highest node inside the range; the values on either side of it are then
+        // bounded by only one end of the range
+        while let Some(node) = tree.root() {
+            let value = node.entry.borrow();
+            if !after_start(range.start_bound(), value) {
+                tree = &node.right;
+            } else if !before_end(range.end_bound(), value) {
+                tree = &node.left;
+            } else {
+                let left = fold_from(&node.left, range.start_bound());
+                let left = A::combine(&left, &A::summarize(&node.entry));
+                return A::combine(&left, &fold_to(&node.right, range.end_bound()));
+            }
+        }
+        A::identity()
+    }
+
+    /// Returns an iterator over references to the values of the tree, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+}
+
+impl<T: Ord, A: Augment<T>> AugmentedTree<T, A>
+where
+    A::Summary: PartialEq,
+{
+    /// Verifies that the tree is a valid balanced binary search tree whose cached heights and
+    /// summaries are correct
+    pub fn validate(&self) -> bool {
+        self.0.validate_by(T::cmp)
+    }
+}
+
+/// Returns the summary of the values of `tree` that are not below `start`
+fn fold_from<T, A, Q>(mut tree: &Avl<T, A>, start: Bound<&Q>) -> A::Summary
+where
+    T: Ord + Borrow<Q>,
+    A: Augment<T>,
+    Q: Ord + ?Sized,
+{
+    let mut summary = A::identity();
+    while let Some(node) = tree.root() {
+        if after_start(start, node.entry.borrow()) {
+            // Nodes visited earlier hold values to the right of this one
+            let right = A::combine(&A::summarize(&node.entry), &node.right.summary());
+            summary = A::combine(&right, &summary);
+            tree = &node.left;
+        } else {
+            tree = &node.right;
+        }
+    }
+    summary
+}
+
+/// Returns the summary of the values of `tree` that are not above `end`
+fn fold_to<T, A, Q>(mut tree: &Avl<T, A>, end: Bound<&Q>) -> A::Summary
+where
+    T: Ord + Borrow<Q>,
+    A: Augment<T>,
+    Q: Ord + ?Sized,
+{
+    let mut summary = A::identity();
+    while let Some(node) = tree.root() {
+        if before_end(end, node.entry.borrow()) {
+            let left = A::combine(&node.left.summary(), &A::summarize(&node.entry));
+            summary = A::combine(&summary, &left);
+            tree = &node.right;
+        } else {
+            tree = &node.left;
+        }
+    }
+    summary
+}
+
+impl<T: Ord, A: Augment<T>> Default for AugmentedTree<T, A> {
+    fn default() -> Self {
+        AugmentedTree::new()
+    }
+}
+
+impl<T: Ord + Clone, A: Augment<T>> Clone for AugmentedTree<T, A> {
+    fn clone(&self) -> Self {
+        AugmentedTree(self.0.clone())
+    }
+}
+
+impl<T: Ord, A: Augment<T>> FromIterator<T> for AugmentedTree<T, A> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = AugmentedTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{augment::Count, avl_core::Avl};
+
+/// An AVL tree that caches the height and size of every subtree in its root node.
+///
+/// `TreeNode<T>` recomputes subtree heights on every rebalance, which makes each insertion linear in
+/// the size of the tree. Keeping the height next to each value, and fixing it up whenever a node's
+/// children change, brings insertion and removal down to O(log n). The cached sizes answer
+/// order-statistic queries such as `rank` and `select` in O(log n) as well.
+#[derive(Clone, Debug)]
+pub struct AvlTree<T: Ord>(pub(crate) Avl<T, Count>);
+
+impl<T: Ord> AvlTree<T> {
+    /// Creates a new empty tree
+    pub fn new() -> Self {
+        AvlTree(Avl::new())
+    }
+
+    /// Returns the height of the tree in O(1)
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Returns the number of values in the tree in O(1)
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the tree contains `value`
+    pub fn contains(&self, value: &T) -> bool {
+        self.0.find(|entry| value.cmp(entry)).is_some()
+    }
+
+    /// Returns an iterator over references to the values of the tree, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    /// Returns the smallest value in the tree, or `None` if the tree is empty
+    pub fn min(&self) -> Option<&T> {
+        self.0.first()
+    }
+
+    /// Returns the largest value in the tree, or `None` if the tree is empty
+    pub fn max(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    /// Inserts `value` into the tree. If the value already exists in the tree, the function does
+    /// nothing.
+    ///
+    /// After insertion, the tree is rebalanced if necessary
+    pub fn insert(&mut self, value: T) {
+        let _ = self.0.insert_by(value, &T::cmp);
+    }
+
+    /// Removes `value` from the tree and returns it, or `None` if the value is not in the tree.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        self.0.remove_by(&|entry| value.cmp(entry))
+    }
+
+    /// Removes the smallest value from the tree and returns it, or `None` if the tree is empty
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.0.pop_first()
+    }
+
+    /// Removes the largest value from the tree and returns it, or `None` if the tree is empty
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.0.pop_last()
+    }
+
+    /// Verifies that the tree is a valid balanced binary search tree whose cached heights and
+    /// sizes are correct
+    pub fn validate(&self) -> bool {
+        self.0.validate_by(T::cmp)
+    }
+}
+
+// Order statistics. Each query follows a single path from the root, steering by the cached
+// subtree sizes, so they run in O(log n).
+impl<T: Ord> AvlTree<T> {
+    /// Returns the number of values in the tree that are smaller than `value`, i.e. the index
+    /// `value` has, or would have, in ascending order
+    pub fn rank(&self, value: &T) -> usize {
+        self.0.rank_by(|entry| value.cmp(entry)).0
+    }
+
+    /// Returns the `k`th smallest value of the tree, counting from zero, or `None` if the tree
+    /// has `k` values or fewer
+    pub fn select(&self, k: usize) -> Option<&T> {
+        self.0.select(k)
+    }
+
+    /// Returns the median of the tree, or `None` if the tree is empty. For an even number of
+    /// values this is the lower of the two middle values.
+    pub fn median(&self) -> Option<&T> {
+        self.select(self.len().checked_sub(1)? / 2)
+    }
+}
+
+impl<T: Ord> Default for AvlTree<T> {
+    fn default() -> Self {
+        AvlTree::new()
+    }
+}
+
+impl<T: Ord> PartialEq for AvlTree<T> {
+    /// Two trees are equal if they have the same shape and values
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Ord> Eq for AvlTree<T> {}
+
+impl<T: Ord> FromIterator<T> for AvlTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for AvlTree<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T: Ord> From<AvlTree<T>> for Vec<T> {
+    fn from(tree: AvlTree<T>) -> Self {
+        tree.0.into_vec()
+    }
+}
@@ -0,0 +1,595 @@
+use std::{cmp::Ordering, fmt::Debug, mem, ops::Deref};
+
+use crate::{
+    augment::{Augment, Count},
+    join::Join,
+    persistent_list::PointerKind,
+    tree::{Rebalance, Shape},
+};
+
+/// How the nodes of an `Avl` tree are owned
+pub(crate) trait NodeKind {
+    type Pointer<U>: Deref<Target = U>;
+
+    /// Returns a pointer to a copy of the pointee, or to the pointee itself if pointers of this
+    /// kind can share it
+    fn clone_pointer<U: Clone>(pointer: &Self::Pointer<U>) -> Self::Pointer<U>;
+}
+
+/// The operations an `Avl` tree needs to take apart and rebuild nodes of type `U`
+pub(crate) trait NodePointer<U>: NodeKind {
+    /// Moves `node` into a new pointer
+    fn new_node(node: U) -> Self::Pointer<U>;
+
+    /// Returns a mutable reference to the node, first copying it if other pointers share it
+    fn node_mut(pointer: &mut Self::Pointer<U>) -> &mut U;
+
+    /// Takes the node out of `pointer`, copying it if other pointers share it
+    fn into_node(pointer: Self::Pointer<U>) -> U;
+}
+
+/// Owns every node through a `Box`, so nodes are never shared and are updated in place
+pub(crate) struct BoxK;
+
+impl NodeKind for BoxK {
+    type Pointer<U> = Box<U>;
+
+    fn clone_pointer<U: Clone>(pointer: &Box<U>) -> Box<U> {
+        pointer.clone()
+    }
+}
+
+impl<U> NodePointer<U> for BoxK {
+    fn new_node(node: U) -> Box<U> {
+        Box::new(node)
+    }
+
+    fn node_mut(pointer: &mut Box<U>) -> &mut U {
+        pointer
+    }
+
+    fn into_node(pointer: Box<U>) -> U {
+        *pointer
+    }
+}
+
+// Shared pointers let versions of a tree share nodes: a node is copied only when one version
+// changes it while others still point to it
+impl<P: PointerKind> NodeKind for P {
+    type Pointer<U> = P::Pointer<U>;
+
+    fn clone_pointer<U: Clone>(pointer: &P::Pointer<U>) -> P::Pointer<U> {
+        pointer.clone()
+    }
+}
+
+impl<U: Clone, P: PointerKind> NodePointer<U> for P {
+    fn new_node(node: U) -> P::Pointer<U> {
+        P::new(node)
+    }
+
+    fn node_mut(pointer: &mut P::Pointer<U>) -> &mut U {
+        P::make_mut(pointer)
+    }
+
+    fn into_node(pointer: P::Pointer<U>) -> U {
+        P::try_unwrap(pointer).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+/// An AVL tree of entries of type `E` that caches, in every node, the height of its subtree and
+/// the summary `A` of the entries in it. Nodes are owned through pointers of kind `P`.
+///
+/// This is the shared core of the crate's AVL trees: each of them picks its entry type and
+/// summary, and passes the comparison that orders its entries to the operations that search the
+/// tree. The rotations keep the cached heights and summaries up to date, so every tree gets them
+/// for free.
+pub(crate) struct Avl<E, A: Augment<E>, P: NodeKind = BoxK>(Option<P::Pointer<Node<E, A, P>>>);
+
+pub(crate) struct Node<E, A: Augment<E>, P: NodeKind = BoxK> {
+    pub(crate) entry: E,
+    pub(crate) height: usize,
+    // Summary of the entries in this subtree, in order
+    pub(crate) summary: A::Summary,
+    pub(crate) left: Avl<E, A, P>,
+    pub(crate) right: Avl<E, A, P>,
+}
+
+impl<E, A: Augment<E>, P: NodeKind> Node<E, A, P> {
+    /// Recomputes the cached height and summary from the children; called whenever they change
+    fn update(&mut self) {
+        self.height = 1 + self.left.height().max(self.right.height());
+        let left = A::combine(&self.left.summary(), &A::summarize(&self.entry));
+        self.summary = A::combine(&left, &self.right.summary());
+    }
+}
+
+impl<E, A: Augment<E>, P: NodeKind> Avl<E, A, P> {
+    /// Creates a new empty tree
+    pub(crate) fn new() -> Self {
+        Avl(None)
+    }
+
+    /// Returns the root node, or `None` if the tree is empty
+    pub(crate) fn root(&self) -> Option<&Node<E, A, P>> {
+        self.0.as_deref()
+    }
+
+    /// Returns the height of the tree
+    pub(crate) fn height(&self) -> usize {
+        self.root().map_or(0, |node| node.height)
+    }
+
+    /// Returns `true` if the tree contains no entries
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the summary of all entries in the tree
+    pub(crate) fn summary(&self) -> A::Summary {
+        self.root()
+            .map_or_else(A::identity, |node| node.summary.clone())
+    }
+
+    /// Returns the entry for which `target` returns `Ordering::Equal`, or `None` if there is none.
+    /// `target` compares the entry searched for with the entry it is given.
+    pub(crate) fn find(&self, target: impl Fn(&E) -> Ordering) -> Option<&E> {
+        let mut tree = self;
+        while let Some(node) = tree.root() {
+            tree = match target(&node.entry) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return Some(&node.entry),
+            };
+        }
+        None
+    }
+
+    /// Returns the first entry of the tree, or `None` if the tree is empty
+    pub(crate) fn first(&self) -> Option<&E> {
+        let mut node = self.root()?;
+        while let Some(left) = node.left.root() {
+            node = left;
+        }
+        Some(&node.entry)
+    }
+
+    /// Returns the last entry of the tree, or `None` if the tree is empty
+    pub(crate) fn last(&self) -> Option<&E> {
+        let mut node = self.root()?;
+        while let Some(right) = node.right.root() {
+            node = right;
+        }
+        Some(&node.entry)
+    }
+
+    /// Returns an iterator over references to the entries of the tree, in order
+    pub(crate) fn iter(&self) -> Iter<'_, E, A, P> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(self);
+        iter
+    }
+}
+
+impl<E, A: Augment<E>, P: NodeKind> Avl<E, A, P>
+where
+    A::Summary: PartialEq,
+{
+    /// Verifies that the tree is balanced, that its entries are in strictly ascending order
+    /// under `cmp`, and that its cached heights and summaries are correct
+    pub(crate) fn validate_by(&self, cmp: impl Fn(&E, &E) -> Ordering) -> bool {
+        /// Returns the height and summary of `tree` if it is valid and all its entries lie
+        /// between `min` and `max`
+        fn check<'a, E, A: Augment<E>, P: NodeKind>(
+            tree: &'a Avl<E, A, P>,
+            min: Option<&'a E>,
+            max: Option<&'a E>,
+            cmp: &impl Fn(&E, &E) -> Ordering,
+        ) -> Option<(usize, A::Summary)>
+        where
+            A::Summary: PartialEq,
+        {
+            let node = match tree.root() {
+                None => return Some((0, A::identity())),
+                Some(node) => node,
+            };
+            if min.is_some_and(|min| cmp(&node.entry, min).is_le())
+                || max.is_some_and(|max| cmp(&node.entry, max).is_ge())
+            {
+                return None;
+            }
+            let (left, left_summary) = check(&node.left, min, Some(&node.entry), cmp)?;
+            let (right, right_summary) = check(&node.right, Some(&node.entry), max, cmp)?;
+            let height = 1 + left.max(right);
+            let summary = A::combine(&left_summary, &A::summarize(&node.entry));
+            let summary = A::combine(&summary, &right_summary);
+            (left.abs_diff(right) <= 1 && node.height == height && node.summary == summary)
+                .then_some((height, summary))
+        }
+        check(self, None, None, &cmp).is_some()
+    }
+}
+
+impl<E, A: Augment<E>, P: NodePointer<Node<E, A, P>>> Avl<E, A, P> {
+    /// Builds a tree with `entry` at the root and `left` and `right` as its subtrees, without
+    /// rebalancing
+    pub(crate) fn node(left: Self, entry: E, right: Self) -> Self {
+        let mut node = Node {
+            summary: A::identity(),
+            entry,
+            height: 1,
+            left,
+            right,
+        };
+        node.update();
+        Avl(Some(P::new_node(node)))
+    }
+
+    /// Inserts `entry` into the tree, placing it with `cmp`. If the tree already holds an entry
+    /// equal to it, the tree is left untouched and `entry` is handed back.
+    ///
+    /// After insertion, the tree is rebalanced if necessary
+    pub(crate) fn insert_by(
+        &mut self,
+        entry: E,
+        cmp: &impl Fn(&E, &E) -> Ordering,
+    ) -> Result<(), E> {
+        let node = match &mut self.0 {
+            None => {
+                *self = Avl::node(Avl::new(), entry, Avl::new());
+                return Ok(());
+            }
+            Some(pointer) => P::node_mut(pointer),
+        };
+        match cmp(&entry, &node.entry) {
+            Ordering::Less => node.left.insert_by(entry, cmp)?,
+            Ordering::Greater => node.right.insert_by(entry, cmp)?,
+            Ordering::Equal => return Err(entry),
+        }
+        node.update();
+        self.rebalance();
+        Ok(())
+    }
+
+    /// Removes the entry for which `target` returns `Ordering::Equal` and returns it, or `None` if
+    /// there is none.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub(crate) fn remove_by(&mut self, target: &impl Fn(&E) -> Ordering) -> Option<E> {
+        let pointer = self.0.as_mut()?;
+        let ordering = target(&pointer.entry);
+        if ordering == Ordering::Equal {
+            return Some(self.remove_root());
+        }
+        let node = P::node_mut(pointer);
+        let removed = match ordering {
+            Ordering::Less => node.left.remove_by(target)?,
+            _ => node.right.remove_by(target)?,
+        };
+        node.update();
+        self.rebalance();
+        Some(removed)
+    }
+
+    /// Returns a mutable reference to the entry for which `target` returns `Ordering::Equal`, or
+    /// `None` if there is none. The caller must not change how the entry is ordered or
+    /// summarized.
+    pub(crate) fn get_mut_by(&mut self, target: &impl Fn(&E) -> Ordering) -> Option<&mut E> {
+        let node = P::node_mut(self.0.as_mut()?);
+        match target(&node.entry) {
+            Ordering::Less => node.left.get_mut_by(target),
+            Ordering::Greater => node.right.get_mut_by(target),
+            Ordering::Equal => Some(&mut node.entry),
+        }
+    }
+
+    /// Removes the first entry from the tree and returns it, or `None` if the tree is empty
+    pub(crate) fn pop_first(&mut self) -> Option<E> {
+        let pointer = self.0.as_mut()?;
+        if pointer.left.is_empty() {
+            let node = P::into_node(self.0.take()?);
+            *self = node.right;
+            return Some(node.entry);
+        }
+        let node = P::node_mut(pointer);
+        let first = node.left.pop_first();
+        node.update();
+        self.rebalance();
+        first
+    }
+
+    /// Removes the last entry from the tree and returns it, or `None` if the tree is empty
+    pub(crate) fn pop_last(&mut self) -> Option<E> {
+        let pointer = self.0.as_mut()?;
+        if pointer.right.is_empty() {
+            let node = P::into_node(self.0.take()?);
+            *self = node.left;
+            return Some(node.entry);
+        }
+        let node = P::node_mut(pointer);
+        let last = node.right.pop_last();
+        node.update();
+        self.rebalance();
+        last
+    }
+
+    /// Removes the entry at the root of the tree, replacing it with its in-order successor, and
+    /// rebalances
+    fn remove_root(&mut self) -> E {
+        let pointer = self
+            .0
+            .take()
+            .expect("cannot remove the root of an empty tree");
+        let mut node = P::into_node(pointer);
+        match node.right.pop_first() {
+            Some(successor) => {
+                let entry = mem::replace(&mut node.entry, successor);
+                node.update();
+                self.0 = Some(P::new_node(node));
+                self.rebalance();
+                entry
+            }
+            None => {
+                *self = node.left;
+                node.entry
+            }
+        }
+    }
+
+    /// Moves the entries of the tree into a vector, in order
+    pub(crate) fn into_vec(self) -> Vec<E> {
+        fn inorder<E, A: Augment<E>, P: NodePointer<Node<E, A, P>>>(
+            tree: Avl<E, A, P>,
+            vec: &mut Vec<E>,
+        ) {
+            if let Some(pointer) = tree.0 {
+                let node = P::into_node(pointer);
+                inorder(node.left, vec);
+                vec.push(node.entry);
+                inorder(node.right, vec);
+            }
+        }
+        let mut vec = Vec::new();
+        inorder(self, &mut vec);
+        vec
+    }
+}
+
+impl<E, A: Augment<E>, P: PointerKind> Avl<E, A, P> {
+    /// Returns a new version of the tree that shares all of its nodes
+    pub(crate) fn share(&self) -> Self {
+        Avl(self.0.clone())
+    }
+
+    /// Returns `true` if both trees share their root node
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(a), Some(b)) => P::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+// Order statistics, for trees that count their entries. Each query follows a single path from the
+// root, steering by the cached subtree sizes, so they run in O(log n).
+impl<E, P: NodeKind> Avl<E, Count, P> {
+    /// Returns the number of entries in the tree in O(1)
+    pub(crate) fn len(&self) -> usize {
+        self.root().map_or(0, |node| node.summary)
+    }
+
+    /// Returns the number of entries that `target` orders before the entry it searches for, and
+    /// whether the tree holds that entry
+    pub(crate) fn rank_by(&self, target: impl Fn(&E) -> Ordering) -> (usize, bool) {
+        let mut rank = 0;
+        let mut tree = self;
+        while let Some(node) = tree.root() {
+            tree = match target(&node.entry) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    rank += node.left.len() + 1;
+                    &node.right
+                }
+                Ordering::Equal => return (rank + node.left.len(), true),
+            };
+        }
+        (rank, false)
+    }
+
+    /// Returns the `k`th entry of the tree, counting from zero, or `None` if the tree has `k`
+    /// entries or fewer
+    pub(crate) fn select(&self, mut k: usize) -> Option<&E> {
+        let mut tree = self;
+        while let Some(node) = tree.root() {
+            let left = node.left.len();
+            tree = match k.cmp(&left) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    &node.right
+                }
+                Ordering::Equal => return Some(&node.entry),
+            };
+        }
+        None
+    }
+}
+
+impl<E, P: NodePointer<Node<E, Count, P>>> Avl<E, Count, P> {
+    /// Returns a mutable reference to the `k`th entry of the tree, counting from zero, or `None`
+    /// if the tree has `k` entries or fewer. The caller must not change how the entry is ordered.
+    pub(crate) fn nth_mut(&mut self, k: usize) -> Option<&mut E> {
+        let node = P::node_mut(self.0.as_mut()?);
+        let left = node.left.len();
+        match k.cmp(&left) {
+            Ordering::Less => node.left.nth_mut(k),
+            Ordering::Greater => node.right.nth_mut(k - left - 1),
+            Ordering::Equal => Some(&mut node.entry),
+        }
+    }
+}
+
+impl<E, A: Augment<E>, P: NodePointer<Node<E, A, P>>> Rebalance for Avl<E, A, P> {
+    fn balance_factor(&self) -> i32 {
+        match self.root() {
+            None => 0,
+            Some(node) => node.left.height() as i32 - node.right.height() as i32,
+        }
+    }
+
+    fn left_rotate(&mut self) {
+        if let Some(mut root_pointer) = self.0.take() {
+            let root = P::node_mut(&mut root_pointer);
+            match root.right.0.take() {
+                Some(mut pivot_pointer) => {
+                    let pivot = P::node_mut(&mut pivot_pointer);
+                    root.right = mem::take(&mut pivot.left);
+                    root.update();
+                    pivot.left = Avl(Some(root_pointer));
+                    pivot.update();
+                    self.0 = Some(pivot_pointer);
+                }
+                None => self.0 = Some(root_pointer),
+            }
+        }
+    }
+
+    fn right_rotate(&mut self) {
+        if let Some(mut root_pointer) = self.0.take() {
+            let root = P::node_mut(&mut root_pointer);
+            match root.left.0.take() {
+                Some(mut pivot_pointer) => {
+                    let pivot = P::node_mut(&mut pivot_pointer);
+                    root.left = mem::take(&mut pivot.right);
+                    root.update();
+                    pivot.right = Avl(Some(root_pointer));
+                    pivot.update();
+                    self.0 = Some(pivot_pointer);
+                }
+                None => self.0 = Some(root_pointer),
+            }
+        }
+    }
+
+    fn children_mut(&mut self) -> Option<(&mut Self, &mut Self)> {
+        let node = P::node_mut(self.0.as_mut()?);
+        Some((&mut node.left, &mut node.right))
+    }
+}
+
+impl<E: Ord, A: Augment<E>, P: NodePointer<Node<E, A, P>>> Join for Avl<E, A, P> {
+    fn empty() -> Self {
+        Avl::new()
+    }
+
+    fn height(&self) -> usize {
+        Avl::height(self)
+    }
+
+    fn node(left: Self, entry: E, right: Self) -> Self {
+        Avl::node(left, entry, right)
+    }
+
+    fn expose(self) -> Option<(Self, E, Self)> {
+        self.0.map(|pointer| {
+            let node = P::into_node(pointer);
+            (node.left, node.entry, node.right)
+        })
+    }
+}
+
+impl<E, A: Augment<E>, P: NodeKind> Shape for Avl<E, A, P> {
+    type Value = E;
+
+    fn parts(&self) -> Option<(&Self, &E, &Self)> {
+        self.root()
+            .map(|node| (&node.left, &node.entry, &node.right))
+    }
+}
+
+impl<E, A: Augment<E>, P: NodeKind> Default for Avl<E, A, P> {
+    fn default() -> Self {
+        Avl::new()
+    }
+}
+
+impl<E: Clone, A: Augment<E>, P: NodeKind> Clone for Node<E, A, P> {
+    /// Copies the entry and the summary; the children are copied or shared as `P` dictates
+    fn clone(&self) -> Self {
+        Node {
+            entry: self.entry.clone(),
+            height: self.height,
+            summary: self.summary.clone(),
+            left: self.left.clone(),
+            right: self.right.clone(),
+        }
+    }
+}
+
+impl<E: Clone, A: Augment<E>, P: NodeKind> Clone for Avl<E, A, P> {
+    fn clone(&self) -> Self {
+        Avl(self.0.as_ref().map(P::clone_pointer))
+    }
+}
+
+impl<E: PartialEq, A: Augment<E>, P: NodeKind> PartialEq for Avl<E, A, P> {
+    /// Two trees are equal if they have the same shape and entries
+    fn eq(&self, other: &Self) -> bool {
+        match (self.root(), other.root()) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.entry == b.entry && a.left == b.left && a.right == b.right,
+            _ => false,
+        }
+    }
+}
+
+impl<E: Debug, A: Augment<E>, P: NodeKind> Debug for Avl<E, A, P>
+where
+    A::Summary: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Avl").field(&self.root()).finish()
+    }
+}
+
+impl<E: Debug, A: Augment<E>, P: NodeKind> Debug for Node<E, A, P>
+where
+    A::Summary: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("entry", &self.entry)
+            .field("height", &self.height)
+            .field("summary", &self.summary)
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+/// Borrowing iterator over the entries of an `Avl` tree, created by [`Avl::iter`]
+pub(crate) struct Iter<'a, E, A: Augment<E>, P: NodeKind = BoxK> {
+    // Nodes whose entry is still to be yielded; their right subtrees have not been visited yet
+    stack: Vec<&'a Node<E, A, P>>,
+}
+
+impl<'a, E, A: Augment<E>, P: NodeKind> Iter<'a, E, A, P> {
+    /// Pushes the root of `tree` and its chain of left children onto the stack
+    fn push_left_spine(&mut self, mut tree: &'a Avl<E, A, P>) {
+        while let Some(node) = tree.root() {
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+}
+
+impl<'a, E, A: Augment<E>, P: NodeKind> Iterator for Iter<'a, E, A, P> {
+    type Item = &'a E;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some(&node.entry)
+    }
+}
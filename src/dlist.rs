@@ -0,0 +1,340 @@
+use std::{fmt::Display, iter::FusedIterator, mem};
+
+/// A doubly linked list.
+///
+/// Nodes live in a slot arena and refer to their neighbours by index, which keeps the list free of
+/// `unsafe` and reference cycles while still giving O(1) access to both ends.
+pub struct DList<T> {
+    slots: Vec<Slot<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    // First slot of the free list threaded through `Slot::Free`
+    free: Option<usize>,
+    len: usize,
+}
+
+enum Slot<T> {
+    Occupied(Node<T>),
+    Free(Option<usize>),
+}
+
+struct Node<T> {
+    value: T,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+// Required methods for `DList<T>`, mirroring `ListNode<T>`
+impl<T> DList<T> {
+    /// Creates a new empty list
+    pub fn new() -> Self {
+        DList {
+            slots: Vec::new(),
+            head: None,
+            tail: None,
+            free: None,
+            len: 0,
+        }
+    }
+
+    /// Appends a new node with value `value` to the end of the list and returns the list, so
+    /// insertions can be chained
+    pub fn insert(&mut self, value: T) -> &mut Self {
+        self.push_back(value);
+        self
+    }
+
+    /// Reverses the list in place, in time linear in its length
+    pub fn reverse(&mut self) {
+        // Follow the links rather than scanning the arena, which may hold many free slots
+        let mut current = self.head;
+        while let Some(index) = current {
+            let node = self.node_mut(index);
+            mem::swap(&mut node.prev, &mut node.next);
+            current = node.prev;
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Deletes the first node from the list
+    pub fn delete(&mut self) {
+        self.pop_front();
+    }
+}
+
+// Double-ended operations on `DList<T>`
+impl<T> DList<T> {
+    /// Returns the number of values in the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list contains no values
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the first value, or `None` if the list is empty
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|i| &self.node(i).value)
+    }
+
+    /// Returns a reference to the last value, or `None` if the list is empty
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|i| &self.node(i).value)
+    }
+
+    /// Prepends `value` to the front of the list
+    pub fn push_front(&mut self, value: T) {
+        let index = self.alloc(Node {
+            value,
+            prev: None,
+            next: self.head,
+        });
+        match self.head {
+            Some(head) => self.node_mut(head).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+        self.len += 1;
+    }
+
+    /// Appends `value` to the back of the list
+    pub fn push_back(&mut self, value: T) {
+        let index = self.alloc(Node {
+            value,
+            prev: self.tail,
+            next: None,
+        });
+        match self.tail {
+            Some(tail) => self.node_mut(tail).next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        self.len += 1;
+    }
+
+    /// Removes the first value and returns it, or `None` if the list is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.release(self.head?);
+        self.head = node.next;
+        match self.head {
+            Some(head) => self.node_mut(head).prev = None,
+            None => self.tail = None,
+        }
+        Some(node.value)
+    }
+
+    /// Removes the last value and returns it, or `None` if the list is empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        let node = self.release(self.tail?);
+        self.tail = node.prev;
+        match self.tail {
+            Some(tail) => self.node_mut(tail).next = None,
+            None => self.head = None,
+        }
+        Some(node.value)
+    }
+
+    /// Returns a double-ended iterator over references to the values in the list
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    fn node(&self, index: usize) -> &Node<T> {
+        match &self.slots[index] {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("linked slot {} is free", index),
+        }
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<T> {
+        match &mut self.slots[index] {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("linked slot {} is free", index),
+        }
+    }
+
+    /// Stores `node` in a free slot, growing the arena if there is none, and returns its index
+    fn alloc(&mut self, node: Node<T>) -> usize {
+        match self.free {
+            Some(index) => {
+                match mem::replace(&mut self.slots[index], Slot::Occupied(node)) {
+                    Slot::Free(next) => self.free = next,
+                    Slot::Occupied(_) => unreachable!("free slot {} is occupied", index),
+                }
+                index
+            }
+            None => {
+                self.slots.push(Slot::Occupied(node));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Takes the node out of slot `index` and puts the slot on the free list
+    fn release(&mut self, index: usize) -> Node<T> {
+        let node = match mem::replace(&mut self.slots[index], Slot::Free(self.free)) {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("linked slot {} is free", index),
+        };
+        self.free = Some(index);
+        self.len -= 1;
+        if self.len == 0 {
+            // Nothing is linked any more, so the whole arena can be reused from scratch
+            self.slots.clear();
+            self.free = None;
+        }
+        node
+    }
+}
+
+impl<T> Default for DList<T> {
+    fn default() -> Self {
+        DList::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DList<T> {}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for DList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display> Display for DList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for value in self.iter() {
+            write!(f, "{} <-> ", value)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
+impl<T> From<Vec<T>> for DList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> From<DList<T>> for Vec<T> {
+    fn from(list: DList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for DList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+/// Borrowing double-ended iterator over a `DList<T>`, created by [`DList::iter`]
+pub struct Iter<'a, T> {
+    list: &'a DList<T>,
+    front: Option<usize>,
+    back: Option<usize>,
+    // Values left between `front` and `back`, so the two ends know when they have met
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.list.node(self.front?);
+        self.front = node.next;
+        self.len -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.list.node(self.back?);
+        self.back = node.prev;
+        self.len -= 1;
+        Some(&node.value)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Consuming double-ended iterator over a `DList<T>`, created by `into_iter`
+pub struct IntoIter<T> {
+    list: DList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    cmp::Ordering,
+    ops::{Bound, Range},
+};
+
+use crate::{
+    augment::{Augment, Count},
+    avl_core::{Avl, Node},
+};
+
+/// A set of half-open intervals `start..end`, stored in an AVL tree ordered by start and then end.
+///
+/// Every node also caches the number of intervals and the largest end point in its subtree. The
+/// end point lets overlap queries skip every subtree whose intervals all end before the query
+/// begins. Intervals with `start >= end`
+/// are empty: they can be stored but overlap nothing.
+#[derive(Clone, Debug)]
+pub struct IntervalTree<T: Ord + Clone>(Avl<Range<T>, Extent>);
+
+/// The summary cached in every node: the number of intervals and their largest end point
+type Extent = (Count, MaxEnd);
+
+/// Summarizes intervals by their largest end point, or `None` for no intervals
+struct MaxEnd;
+
+impl<T: Ord + Clone> Augment<Range<T>> for MaxEnd {
+    type Summary = Option<T>;
+
+    fn identity() -> Option<T> {
+        None
+    }
+
+    fn summarize(interval: &Range<T>) -> Option<T> {
+        Some(interval.end.clone())
+    }
+
+    fn combine(left: &Option<T>, right: &Option<T>) -> Option<T> {
+        left.as_ref().max(right.as_ref()).cloned()
+    }
+}
+
+/// Orders intervals by start, then by end
+fn cmp_intervals<T: Ord>(a: &Range<T>, b: &Range<T>) -> Ordering {
+    a.start.cmp(&b.start).then_with(|| a.end.cmp(&b.end))
+}
+
+impl<T: Ord + Clone> IntervalTree<T> {
+    /// Creates a new empty tree
+    pub fn new() -> Self {
+        IntervalTree(Avl::new())
+    }
+
+    /// Returns the number of intervals in the tree
+    pub fn len(&self) -> usize {
+        self.0.root().map_or(0, |node| node.summary.0)
+    }
+
+    /// Returns `true` if the tree contains no intervals
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the height of the tree
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Returns the largest end point of any interval in the tree, or `None` if the tree is empty
+    pub fn max_end(&self) -> Option<&T> {
+        self.0.root().and_then(|node| node.summary.1.as_ref())
+    }
+
+    /// Inserts `interval` into the tree and returns `true`, or returns `false` if the tree already
+    /// contains it.
+    ///
+    /// After insertion, the tree is rebalanced if necessary
+    pub fn insert(&mut self, interval: Range<T>) -> bool {
+        self.0.insert_by(interval, &cmp_intervals).is_ok()
+    }
+
+    /// Removes `interval` from the tree and returns `true`, or returns `false` if the tree does
+    /// not contain it.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub fn remove(&mut self, interval: &Range<T>) -> bool {
+        self.0
+            .remove_by(&|entry| cmp_intervals(interval, entry))
+            .is_some()
+    }
+
+    /// Returns an iterator over the intervals that contain `point`, ordered by start and then end
+    pub fn overlapping(&self, point: T) -> Overlapping<'_, T> {
+        Overlapping::new(self, point.clone(), Bound::Included(point))
+    }
+
+    /// Returns an iterator over the intervals that share at least one point with `range`, ordered
+    /// by start and then end
+    pub fn overlapping_range(&self, range: Range<T>) -> Overlapping<'_, T> {
+        let empty = range.is_empty();
+        let mut iter = Overlapping::new(self, range.start, Bound::Excluded(range.end));
+        if empty {
+            // An empty range has no point in common with any interval
+            iter.stack.clear();
+        }
+        iter
+    }
+
+    /// Returns an iterator over all intervals in the tree, ordered by start and then end
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.0.iter()
+    }
+
+    /// Verifies that the tree is a valid balanced binary search tree whose cached heights and
+    /// largest end points are correct
+    pub fn validate(&self) -> bool {
+        self.0.validate_by(cmp_intervals)
+    }
+}
+
+impl<T: Ord + Clone> Default for IntervalTree<T> {
+    fn default() -> Self {
+        IntervalTree::new()
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<Range<T>> for IntervalTree<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut tree = IntervalTree::new();
+        for interval in iter {
+            tree.insert(interval);
+        }
+        tree
+    }
+}
+
+/// Iterator over the intervals of an `IntervalTree<T>` that overlap a query, created by
+/// [`IntervalTree::overlapping`] and [`IntervalTree::overlapping_range`]
+pub struct Overlapping<'a, T: Ord + Clone> {
+    // Nodes still to be visited in order; their right subtrees have not been visited yet
+    stack: Vec<&'a Node<Range<T>, Extent>>,
+    // An interval overlaps the query if it ends after `from` and starts before `to`
+    from: T,
+    to: Bound<T>,
+}
+
+impl<'a, T: Ord + Clone> Overlapping<'a, T> {
+    fn new(tree: &'a IntervalTree<T>, from: T, to: Bound<T>) -> Self {
+        let mut iter = Overlapping {
+            stack: Vec::new(),
+            from,
+            to,
+        };
+        iter.push_left_spine(&tree.0);
+        iter
+    }
+
+    /// Pushes the root of `tree` and its chain of left children onto the stack, stopping at the
+    /// first subtree whose intervals all end before the query begins
+    fn push_left_spine(&mut self, mut tree: &'a Avl<Range<T>, Extent>) {
+        while let Some(node) = tree.root() {
+            let max_end = node.summary.1.as_ref();
+            if max_end.is_none_or(|max_end| *max_end <= self.from) {
+                break;
+            }
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+
+    /// Returns `true` if an interval starting at `start` starts before the query ends
+    fn starts_in_time(&self, start: &T) -> bool {
+        match &self.to {
+            Bound::Included(to) => start <= to,
+            Bound::Excluded(to) => start < to,
+            Bound::Unbounded => true,
+        }
+    }
+}
+
+impl<'a, T: Ord + Clone> Iterator for Overlapping<'a, T> {
+    type Item = &'a Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.starts_in_time(&node.entry.start) {
+                // Everything left to visit starts even later
+                self.stack.clear();
+                return None;
+            }
+            self.push_left_spine(&node.right);
+            if node.entry.end > self.from && node.entry.start < node.entry.end {
+                return Some(&node.entry);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+
+use crate::{
+    augment::Count,
+    avl::AvlTree,
+    avl_core::Avl,
+    tree::{Rebalance, Shape, TreeNode},
+};
+
+/// A balanced binary search tree representation that can be taken apart into its root and
+/// subtrees and put back together.
+///
+/// `split` and `join` only need these operations plus rebalancing, so every representation shares
+/// them, and the set algebra built on top. Their running time assumes `height` is O(1).
+pub(crate) trait Join: Rebalance + Shape<Value: Ord> + Sized {
+    /// Returns an empty tree
+    fn empty() -> Self;
+
+    /// Returns the height of the tree
+    fn height(&self) -> usize;
+
+    /// Builds a tree with `value` at the root and `left` and `right` as its subtrees, without
+    /// rebalancing
+    fn node(left: Self, value: Self::Value, right: Self) -> Self;
+
+    /// Takes the tree apart into its left subtree, root value and right subtree, or returns
+    /// `None` if the tree is empty
+    fn expose(self) -> Option<(Self, Self::Value, Self)>;
+}
+
+/// Joins `left`, `value` and `right` into one balanced tree, assuming every value of `left` is
+/// smaller than `value` and every value of `right` is greater. Runs in O(difference in height).
+pub(crate) fn join<J: Join>(left: J, value: J::Value, right: J) -> J {
+    let (left_height, right_height) = (left.height(), right.height());
+    if left_height > right_height + 1 {
+        // Descend the right spine of `left` until the subtree there is as short as `right`
+        let (ll, lv, lr) = left.expose().expect("a taller tree is not empty");
+        let mut tree = J::node(ll, lv, join(lr, value, right));
+        tree.rebalance();
+        tree
+    } else if right_height > left_height + 1 {
+        let (rl, rv, rr) = right.expose().expect("a taller tree is not empty");
+        let mut tree = J::node(join(left, value, rl), rv, rr);
+        tree.rebalance();
+        tree
+    } else {
+        J::node(left, value, right)
+    }
+}
+
+/// Joins `left` and `right` into one balanced tree, assuming every value of `left` is smaller
+/// than every value of `right`
+fn join2<J: Join>(left: J, right: J) -> J {
+    match split_last(left) {
+        None => right,
+        Some((left, last)) => join(left, last, right),
+    }
+}
+
+/// Splits `tree` into the values smaller than `value`, the value equal to it if any, and the
+/// values greater than it
+pub(crate) fn split<J: Join>(tree: J, value: &J::Value) -> (J, Option<J::Value>, J) {
+    let (left, root, right) = match tree.expose() {
+        None => return (J::empty(), None, J::empty()),
+        Some(parts) => parts,
+    };
+    match value.cmp(&root) {
+        Ordering::Less => {
+            let (ll, found, lr) = split(left, value);
+            (ll, found, join(lr, root, right))
+        }
+        Ordering::Greater => {
+            let (rl, found, rr) = split(right, value);
+            (join(left, root, rl), found, rr)
+        }
+        Ordering::Equal => (left, Some(root), right),
+    }
+}
+
+/// Removes the largest value of `tree` and returns the rest of the tree with it, or `None` if the
+/// tree is empty
+fn split_last<J: Join>(tree: J) -> Option<(J, J::Value)> {
+    let (left, root, right) = tree.expose()?;
+    Some(match split_last(right) {
+        None => (left, root),
+        Some((right, last)) => (join(left, root, right), last),
+    })
+}
+
+/// Returns a tree holding the values that are in `a` or `b`. Where both hold an equal value, the
+/// one from `a` is kept.
+pub(crate) fn union<J: Join>(a: J, b: J) -> J {
+    let (al, av, ar) = match a.expose() {
+        None => return b,
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return J::node(al, av, ar);
+    }
+    let (bl, _, br) = split(b, &av);
+    join(union(al, bl), av, union(ar, br))
+}
+
+/// Returns a tree holding the values of `a` that are also in `b`
+pub(crate) fn intersection<J: Join>(a: J, b: J) -> J {
+    let (al, av, ar) = match a.expose() {
+        None => return J::empty(),
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return J::empty();
+    }
+    let (bl, found, br) = split(b, &av);
+    let (left, right) = (intersection(al, bl), intersection(ar, br));
+    match found {
+        Some(_) => join(left, av, right),
+        None => join2(left, right),
+    }
+}
+
+/// Returns a tree holding the values of `a` that are not in `b`
+pub(crate) fn difference<J: Join>(a: J, b: J) -> J {
+    let (al, av, ar) = match a.expose() {
+        None => return J::empty(),
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return J::node(al, av, ar);
+    }
+    let (bl, found, br) = split(b, &av);
+    let (left, right) = (difference(al, bl), difference(ar, br));
+    match found {
+        Some(_) => join2(left, right),
+        None => join(left, av, right),
+    }
+}
+
+/// Returns a tree holding the values that are in exactly one of `a` and `b`
+pub(crate) fn symmetric_difference<J: Join>(a: J, b: J) -> J {
+    let (al, av, ar) = match a.expose() {
+        None => return b,
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return J::node(al, av, ar);
+    }
+    let (bl, found, br) = split(b, &av);
+    let (left, right) = (symmetric_difference(al, bl), symmetric_difference(ar, br));
+    match found {
+        Some(_) => join2(left, right),
+        None => join(left, av, right),
+    }
+}
+
+/// Returns `true` if every value of `a` is also in `b`, stopping at the first value that is not
+pub(crate) fn is_subset<J: Join>(a: J, b: J) -> bool {
+    let (al, av, ar) = match a.expose() {
+        None => return true,
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return false;
+    }
+    let (bl, found, br) = split(b, &av);
+    found.is_some() && is_subset(al, bl) && is_subset(ar, br)
+}
+
+/// Returns `true` if `a` and `b` have no value in common, stopping at the first shared value
+pub(crate) fn is_disjoint<J: Join>(a: J, b: J) -> bool {
+    let (al, av, ar) = match a.expose() {
+        None => return true,
+        Some(parts) => parts,
+    };
+    if b.parts().is_none() {
+        return true;
+    }
+    let (bl, found, br) = split(b, &av);
+    found.is_none() && is_disjoint(al, bl) && is_disjoint(ar, br)
+}
+
+/// Rebuilds `tree` node for node in another representation, in time linear in its size
+fn convert<A: Join, B: Join + Shape<Value = A::Value>>(tree: A) -> B {
+    match tree.expose() {
+        None => B::empty(),
+        Some((left, value, right)) => B::node(convert(left), value, convert(right)),
+    }
+}
+
+// Set algebra on `TreeNode<T>`. A `TreeNode<T>` has nowhere to cache subtree heights, which
+// `join` needs in O(1), so these methods move both trees into the AVL core behind `AvlTree<T>`, run
+// the algorithms there and move the result back. That adds O(n + m) for the moves to the bounds
+// listed for `AvlTree<T>` below.
+impl<T: Ord> TreeNode<T> {
+    /// Splits the tree into the values smaller than `value`, the value equal to it if any, and
+    /// the values greater than it. Both halves are balanced.
+    pub fn split(self, value: &T) -> (TreeNode<T>, Option<T>, TreeNode<T>) {
+        let (left, found, right) = split(cached(self), value);
+        (convert(left), found, convert(right))
+    }
+
+    /// Joins `left`, `value` and `right` into one balanced tree.
+    ///
+    /// Panics if some value of `left` is not smaller than `value`, or some value of `right` is
+    /// not greater
+    pub fn join(left: TreeNode<T>, value: T, right: TreeNode<T>) -> TreeNode<T> {
+        assert!(
+            left.max().is_none_or(|max| *max < value) && right.min().is_none_or(|min| *min > value),
+            "values passed to `join` must be in ascending order"
+        );
+        convert(join(cached(left), value, cached(right)))
+    }
+
+    /// Returns the union of both trees. Where both hold an equal value, the one from `self` is
+    /// kept.
+    pub fn union(self, other: TreeNode<T>) -> TreeNode<T> {
+        convert(union(cached(self), cached(other)))
+    }
+
+    /// Returns the values of `self` that are also in `other`
+    pub fn intersection(self, other: TreeNode<T>) -> TreeNode<T> {
+        convert(intersection(cached(self), cached(other)))
+    }
+
+    /// Returns the values of `self` that are not in `other`
+    pub fn difference(self, other: TreeNode<T>) -> TreeNode<T> {
+        convert(difference(cached(self), cached(other)))
+    }
+
+    /// Returns the values that are in exactly one of the trees
+    pub fn symmetric_difference(self, other: TreeNode<T>) -> TreeNode<T> {
+        convert(symmetric_difference(cached(self), cached(other)))
+    }
+
+    /// Returns `true` if every value of `self` is also in `other`
+    pub fn is_subset(self, other: TreeNode<T>) -> bool {
+        is_subset(cached(self), cached(other))
+    }
+
+    /// Returns `true` if the trees have no value in common
+    pub fn is_disjoint(self, other: TreeNode<T>) -> bool {
+        is_disjoint(cached(self), cached(other))
+    }
+}
+
+/// Moves `tree` into the AVL core, which caches every subtree height
+fn cached<T: Ord>(tree: TreeNode<T>) -> Avl<T, Count> {
+    convert(tree)
+}
+
+// Set algebra on `AvlTree<T>`. With cached heights, `join` runs in O(difference in height) and
+// `split` in O(log n), so every operation on trees of sizes m <= n below takes O(m log(n / m + 1)).
+// Like `split`, the set algebra consumes the trees it works on.
+impl<T: Ord> AvlTree<T> {
+    /// Splits the tree into the values smaller than `value`, the value equal to it if any, and
+    /// the values greater than it
+    pub fn split(self, value: &T) -> (AvlTree<T>, Option<T>, AvlTree<T>) {
+        let (left, found, right) = split(self.0, value);
+        (AvlTree(left), found, AvlTree(right))
+    }
+
+    /// Joins `left`, `value` and `right` into one balanced tree in O(log n).
+    ///
+    /// Panics if some value of `left` is not smaller than `value`, or some value of `right` is
+    /// not greater
+    pub fn join(left: AvlTree<T>, value: T, right: AvlTree<T>) -> AvlTree<T> {
+        assert!(
+            left.max().is_none_or(|max| *max < value) && right.min().is_none_or(|min| *min > value),
+            "values passed to `join` must be in ascending order"
+        );
+        AvlTree(join(left.0, value, right.0))
+    }
+
+    /// Returns the union of both trees. Where both hold an equal value, the one from `self` is
+    /// kept.
+    pub fn union(self, other: AvlTree<T>) -> AvlTree<T> {
+        AvlTree(union(self.0, other.0))
+    }
+
+    /// Returns the values of `self` that are also in `other`
+    pub fn intersection(self, other: AvlTree<T>) -> AvlTree<T> {
+        AvlTree(intersection(self.0, other.0))
+    }
+
+    /// Returns the values of `self` that are not in `other`
+    pub fn difference(self, other: AvlTree<T>) -> AvlTree<T> {
+        AvlTree(difference(self.0, other.0))
+    }
+
+    /// Returns the values that are in exactly one of the trees
+    pub fn symmetric_difference(self, other: AvlTree<T>) -> AvlTree<T> {
+        AvlTree(symmetric_difference(self.0, other.0))
+    }
+
+    /// Returns `true` if every value of `self` is also in `other`
+    pub fn is_subset(self, other: AvlTree<T>) -> bool {
+        is_subset(self.0, other.0)
+    }
+
+    /// Returns `true` if the trees have no value in common
+    pub fn is_disjoint(self, other: AvlTree<T>) -> bool {
+        is_disjoint(self.0, other.0)
+    }
+}
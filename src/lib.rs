@@ -1,4 +1,17 @@
 #![allow(unused_variables)]
+pub mod augment;
+pub mod avl;
+mod avl_core;
+pub mod dlist;
+pub mod interval;
+mod join;
 pub mod list;
+pub mod persistent_list;
+pub mod persistent_tree;
 pub mod print;
+pub mod range;
+pub mod rbtree;
+pub mod sorted_set;
+pub mod traverse;
+pub mod treap;
 pub mod tree;
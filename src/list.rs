@@ -1,27 +1,59 @@
 #[allow(unused_imports)]
-use std::{fmt::Display, mem};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::Display,
+    hash::Hash,
+    iter::Chain,
+    mem::{self, ManuallyDrop},
+    ptr, slice,
+    str::FromStr,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum ListNode<T> {
+    #[default]
     Nil,
     Cons(T, Box<ListNode<T>>),
 }
 
 impl<T> ListNode<T> {
-    // Use the implementation of this method to guide your implementation of
-    // `insert` and `reverse`
     /// Deletes a node from the list
     pub fn delete(&mut self) {
-        // Temporarily replaces the current node with default value (Nil).
-        // In exchange, we get to take ownership of the current node instead of just
-        // having it by mutable reference.
-        let as_owned: ListNode<T> = mem::take(self);
-        match as_owned {
-            ListNode::Nil => {}
-            ListNode::Cons(_, next) => {
-                // Write the next node to the current node
-                *self = *next;
-            }
+        self.remove();
+    }
+
+    /// Deletes a node from the list and returns its value, or `None` if `self` is `Nil`
+    pub fn remove(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+}
+
+// Stack operations on `ListNode<T>`, which treat the front of the list as the top of the stack
+impl<T> ListNode<T> {
+    /// Prepends `value` to the front of the list
+    pub fn push_front(&mut self, value: T) {
+        self.cursor_mut().insert_before(value);
+    }
+
+    /// Removes the first value of the list and returns it, or `None` if the list is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove()
+    }
+
+    /// Returns a reference to the first value of the list, or `None` if the list is empty
+    pub fn peek(&self) -> Option<&T> {
+        match self {
+            ListNode::Nil => None,
+            ListNode::Cons(value, _) => Some(value),
+        }
+    }
+
+    /// Returns a mutable reference to the first value of the list, or `None` if the list is empty
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        match self {
+            ListNode::Nil => None,
+            ListNode::Cons(value, _) => Some(value),
         }
     }
 }
@@ -35,71 +67,621 @@ impl<T> ListNode<T> {
     /// Inserts a new list node with value `value` after `self` and returns a reference to the new
     /// node
     pub fn insert(&mut self, value: T) -> &mut Self {
-      match self {
-          ListNode::Nil => {
-              *self = ListNode::Cons(value, Box::new(ListNode::Nil));
-              self
-          }
-          ListNode::Cons(_, next) => next.insert(value),
-      }
-  }
-  
+        let mut tail = self;
+        while let ListNode::Cons(_, next) = tail {
+            tail = next;
+        }
+        *tail = ListNode::Cons(value, Box::new(ListNode::Nil));
+        tail
+    }
+
     /// Reverses the list in place.
     pub fn reverse(&mut self) {
-      let mut prev = ListNode::Nil;
-      let mut current = mem::take(self);
-  
-      while let ListNode::Cons(value, next) = current {
-          current = *next;
-          prev = ListNode::Cons(value, Box::new(prev));
-      }
-  
-      *self = prev;
-  }
-  
-}
-
-// Implement `Default` for `ListNode<T>`
-impl<T> Default for ListNode<T> {
-    fn default() -> Self {
-        ListNode::Nil
+        let mut prev = ListNode::Nil;
+        let mut current = mem::take(self);
+
+        // Relink each node in front of the already reversed part, reusing its allocation
+        while let ListNode::Cons(_, next) = &mut current {
+            let rest = mem::replace(&mut **next, prev);
+            prev = mem::replace(&mut current, rest);
+        }
+
+        *self = prev;
+    }
+
+    /// Removes every node from the list
+    pub fn clear(&mut self) {
+        *self = ListNode::Nil;
+    }
+}
+
+impl<T> ListNode<T> {
+    /// Takes the first node apart into its value and the rest of the list, or returns `None` if
+    /// the list is empty. `ListNode` implements `Drop`, so patterns can't move its fields out.
+    fn into_parts(self) -> Option<(T, Box<ListNode<T>>)> {
+        let mut node = ManuallyDrop::new(self);
+        match &mut *node {
+            ListNode::Nil => None,
+            // SAFETY: `node` is never used or dropped again, so each field is moved out of it
+            // exactly once
+            ListNode::Cons(value, next) => unsafe { Some((ptr::read(value), ptr::read(next))) },
+        }
+    }
+}
+
+// Implement `Drop` for `ListNode<T>`
+impl<T> Drop for ListNode<T> {
+    /// Unlinks the nodes one at a time, as the compiler-generated drop would recurse once per
+    /// node and overflow the stack on long lists
+    fn drop(&mut self) {
+        let mut rest = match self {
+            ListNode::Nil => return,
+            ListNode::Cons(_, next) => mem::take(&mut **next),
+        };
+        // Each node is dropped with an empty tail, so its own drop returns straight away
+        while let ListNode::Cons(_, next) = &mut rest {
+            rest = mem::take(&mut **next);
+        }
+    }
+}
+
+// Positional operations on `ListNode<T>`
+impl<T> ListNode<T> {
+    /// Returns the number of values in the list
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns `true` if the list contains no values
+    pub fn is_empty(&self) -> bool {
+        matches!(self, ListNode::Nil)
+    }
+
+    /// Returns a reference to the value at `index`, or `None` if `index` is out of range
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// Returns a mutable reference to the value at `index`, or `None` if `index` is out of range
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.cursor_at(index)?.into_current()
+    }
+
+    /// Inserts `value` so that it ends up at `index`, shifting the following values back. Gives
+    /// `value` back if `index` is greater than the length of the list.
+    pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), T> {
+        match self.cursor_at(index) {
+            Some(mut cursor) => {
+                cursor.insert_before(value);
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Removes the value at `index` and returns it, or `None` if `index` is out of range
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        self.cursor_at(index)?.remove_current()
+    }
+
+    /// Splits the list in two at `index`: `self` keeps the first `index` values and the rest are
+    /// returned. Returns `None` if `index` is greater than the length of the list.
+    pub fn split_off(&mut self, index: usize) -> Option<ListNode<T>> {
+        Some(self.cursor_at(index)?.split())
+    }
+
+    /// Moves all values of `other` to the end of `self`, leaving `other` empty
+    pub fn append(&mut self, other: &mut ListNode<T>) {
+        let mut cursor = self.cursor_mut();
+        while cursor.move_next() {}
+        cursor.splice_before(mem::take(other));
+    }
+
+    /// Keeps the first `len` values of the list and drops the rest. Does nothing if the list has
+    /// `len` values or fewer.
+    pub fn truncate(&mut self, len: usize) {
+        self.split_off(len);
+    }
+
+    /// Returns a cursor positioned at `index`, or `None` if `index` is greater than the length of
+    /// the list. A cursor at `index == len` rests on the `Nil` terminator.
+    fn cursor_at(&mut self, index: usize) -> Option<CursorMut<'_, T>> {
+        let mut cursor = self.cursor_mut();
+        while cursor.index() < index {
+            if !cursor.move_next() {
+                return None;
+            }
+        }
+        Some(cursor)
+    }
+}
+
+// Iteration over `ListNode<T>`
+impl<T> ListNode<T> {
+    /// Returns an iterator over references to the values in the list
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self }
+    }
+
+    /// Returns an iterator over mutable references to the values in the list
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: Some(self) }
+    }
+
+    /// Returns a cursor positioned at the first node of the list
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            node: Some(self),
+            index: 0,
+        }
+    }
+}
+
+// Functional combinators on `ListNode<T>`. The plain versions consume the list and reuse its
+// values; the `_ref` versions borrow it and work with references to its values.
+impl<T> ListNode<T> {
+    /// Returns a list of the results of calling `f` on each value, in order
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> ListNode<U> {
+        self.into_iter().map(f).collect()
+    }
+
+    /// Returns a list of the results of calling `f` on a reference to each value, in order
+    pub fn map_ref<U, F: FnMut(&T) -> U>(&self, f: F) -> ListNode<U> {
+        self.iter().map(f).collect()
+    }
+
+    /// Returns a list of the values for which `predicate` returns `true`
+    pub fn filter<F: FnMut(&T) -> bool>(self, predicate: F) -> ListNode<T> {
+        self.into_iter().filter(predicate).collect()
+    }
+
+    /// Returns a list of references to the values for which `predicate` returns `true`
+    pub fn filter_ref<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> ListNode<&T> {
+        self.iter().filter(|value| predicate(value)).collect()
+    }
+
+    /// Returns a list of the `Some` results of calling `f` on each value
+    pub fn filter_map<U, F: FnMut(T) -> Option<U>>(self, f: F) -> ListNode<U> {
+        self.into_iter().filter_map(f).collect()
+    }
+
+    /// Returns a list of the `Some` results of calling `f` on a reference to each value
+    pub fn filter_map_ref<U, F: FnMut(&T) -> Option<U>>(&self, f: F) -> ListNode<U> {
+        self.iter().filter_map(f).collect()
+    }
+
+    /// Combines the values from front to back with `f`, starting from `init`
+    pub fn fold<B, F: FnMut(B, T) -> B>(self, init: B, f: F) -> B {
+        self.into_iter().fold(init, f)
+    }
+
+    /// Combines references to the values from front to back with `f`, starting from `init`
+    pub fn fold_ref<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Returns a list of pairs of values from `self` and `other`, as long as the shorter list
+    pub fn zip<U>(self, other: ListNode<U>) -> ListNode<(T, U)> {
+        self.into_iter().zip(other).collect()
+    }
+
+    /// Returns a list of pairs of references to values from `self` and `other`, as long as the
+    /// shorter list
+    pub fn zip_ref<'a, U>(&'a self, other: &'a ListNode<U>) -> ListNode<(&'a T, &'a U)> {
+        self.iter().zip(other).collect()
+    }
+
+    /// Splits the list into the values for which `predicate` returns `true` and the rest, keeping
+    /// their order
+    pub fn partition<F: FnMut(&T) -> bool>(self, predicate: F) -> (ListNode<T>, ListNode<T>) {
+        partition_into(self, predicate)
+    }
+
+    /// Splits references to the values into those for which `predicate` returns `true` and the
+    /// rest, keeping their order
+    pub fn partition_ref<F: FnMut(&T) -> bool>(
+        &self,
+        mut predicate: F,
+    ) -> (ListNode<&T>, ListNode<&T>) {
+        partition_into(self, |value| predicate(value))
+    }
+
+    /// Removes the values for which `keep` returns `false`, keeping the order of the rest
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        let mut cursor = self.cursor_mut();
+        while let Some(value) = cursor.peek() {
+            if keep(value) {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
+        }
+    }
+
+    /// Returns `true` if `predicate` returns `true` for any value
+    pub fn any<F: FnMut(&T) -> bool>(&self, predicate: F) -> bool {
+        self.iter().any(predicate)
+    }
+
+    /// Returns `true` if `predicate` returns `true` for every value
+    pub fn all<F: FnMut(&T) -> bool>(&self, predicate: F) -> bool {
+        self.iter().all(predicate)
+    }
+
+    /// Returns a reference to the first value for which `predicate` returns `true`
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<&T> {
+        self.iter().find(|value| predicate(value))
+    }
+
+    /// Returns the index of the first value for which `predicate` returns `true`
+    pub fn position<F: FnMut(&T) -> bool>(&self, predicate: F) -> Option<usize> {
+        self.iter().position(predicate)
+    }
+}
+
+impl<A, B> ListNode<(A, B)> {
+    /// Splits a list of pairs into a list of first values and a list of second values
+    pub fn unzip(self) -> (ListNode<A>, ListNode<B>) {
+        let (mut left, mut right) = (ListNode::Nil, ListNode::Nil);
+        let (mut left_tail, mut right_tail) = (&mut left, &mut right);
+        for (a, b) in self {
+            left_tail = left_tail.insert(a);
+            right_tail = right_tail.insert(b);
+        }
+        (left, right)
+    }
+}
+
+/// Splits `values` into those for which `predicate` returns `true` and the rest, appending to the
+/// tail of each list as it goes
+fn partition_into<T, I, F>(values: I, mut predicate: F) -> (ListNode<T>, ListNode<T>)
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+    let (mut matched, mut rest) = (ListNode::Nil, ListNode::Nil);
+    let (mut matched_tail, mut rest_tail) = (&mut matched, &mut rest);
+    for value in values {
+        if predicate(&value) {
+            matched_tail = matched_tail.insert(value);
+        } else {
+            rest_tail = rest_tail.insert(value);
+        }
+    }
+    (matched, rest)
+}
+
+// Sorting and deduplication of `ListNode<T>`
+impl<T> ListNode<T> {
+    /// Sorts the list in ascending order. The sort is stable and relinks the existing nodes, moving
+    /// a value only when it becomes the head of a merged run.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with the comparator `compare`, keeping equal values in their original order.
+    /// If `compare` panics, the list keeps all of its values in an unspecified order.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let len = self.len();
+        merge_sort(self, len, &mut compare, &mut None);
+    }
+
+    /// Sorts the list by the key `key` extracts from each value, keeping values with equal keys in
+    /// their original order
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Merges two sorted lists into one sorted list. Values of `a` come before equal values of `b`.
+    pub fn merge_sorted(a: ListNode<T>, b: ListNode<T>) -> ListNode<T>
+    where
+        T: Ord,
+    {
+        let (mut a, mut b) = (a, Box::new(b));
+        merge(&mut a, &mut b, &mut T::cmp);
+        a
+    }
+
+    /// Removes consecutive repeated values, keeping the first of each run
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive values that map to the same key, keeping the first of each run
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive values for which `same_bucket(value, kept)` returns `true`, where `kept`
+    /// is the value before `value` that was kept
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut node = self;
+        while let ListNode::Cons(kept, next) = node {
+            while let ListNode::Cons(value, _) = &mut **next {
+                if !same_bucket(value, kept) {
+                    break;
+                }
+                next.delete();
+            }
+            node = next;
+        }
+    }
+}
+
+/// Sorts the first `len` nodes of `list`, which must be exactly `len` nodes long.
+///
+/// Cutting a list in two needs a `Nil` terminator for the front half; `spare` holds the one left
+/// over by the last merge so the recursion allocates at most one terminator per level.
+fn merge_sort<T, F: FnMut(&T, &T) -> Ordering>(
+    list: &mut ListNode<T>,
+    len: usize,
+    compare: &mut F,
+    spare: &mut Option<Box<ListNode<T>>>,
+) {
+    if len < 2 {
+        return;
+    }
+    let mut cut = match list {
+        ListNode::Cons(_, next) => next,
+        ListNode::Nil => unreachable!("list is shorter than `len`"),
+    };
+    for _ in 1..len / 2 {
+        match &mut **cut {
+            ListNode::Cons(_, next) => cut = next,
+            ListNode::Nil => unreachable!("list is shorter than `len`"),
+        }
+    }
+    let nil = spare.take().unwrap_or_default();
+    let back = mem::replace(cut, nil);
+    let mut halves = Halves {
+        front: list,
+        back: Some(back),
+    };
+    let Halves { front, back } = &mut halves;
+    let back = back
+        .as_mut()
+        .expect("the back half is only taken once it is merged");
+    merge_sort(front, len / 2, compare, spare);
+    merge_sort(back, len - len / 2, compare, spare);
+    merge(front, back, compare);
+    // The merge left the unneeded terminator in the back half
+    *spare = halves.back.take();
+}
+
+/// The two halves of a list that `merge_sort` has cut apart. If the comparator panics, dropping
+/// this appends `back` to `front` again, so the list being sorted keeps all of its nodes.
+struct Halves<'a, T> {
+    front: &'a mut ListNode<T>,
+    back: Option<Box<ListNode<T>>>,
+}
+
+impl<T> Drop for Halves<'_, T> {
+    fn drop(&mut self) {
+        if let Some(back) = self.back.take() {
+            let mut end = &mut *self.front;
+            while let ListNode::Cons(_, next) = end {
+                end = next;
+            }
+            *end = *back;
+        }
+    }
+}
+
+/// Merges the sorted lists `a` and `b` into `a` by relinking their nodes, keeping values of `a`
+/// before equal values of `b`, and leaves the `Nil` terminator that is no longer needed in `b`.
+///
+/// Every node stays in either `a` or `b` between calls to `compare`, so a panic loses none.
+fn merge<T, F: FnMut(&T, &T) -> Ordering>(
+    a: &mut ListNode<T>,
+    b: &mut Box<ListNode<T>>,
+    compare: &mut F,
+) {
+    // The first node of `a` is not boxed, so if `b` goes first the two trade contents. Afterwards,
+    // `slot_from_b` says whether the chain at `slot` came from `b`.
+    let mut slot_from_b = match takes_other(a, b, compare, false) {
+        None => return,
+        Some(swap) => {
+            if swap {
+                mem::swap(a, &mut **b);
+            }
+            swap
+        }
+    };
+    let mut slot = match a {
+        ListNode::Cons(_, next) => next,
+        ListNode::Nil => unreachable!("`a` was checked to be a `Cons`"),
+    };
+    while let Some(swap) = takes_other(slot, b, compare, slot_from_b) {
+        if swap {
+            mem::swap(slot, b);
+            slot_from_b = !slot_from_b;
+        }
+        match &mut **slot {
+            ListNode::Cons(_, next) => slot = next,
+            ListNode::Nil => unreachable!("`slot` was checked to be a `Cons`"),
+        }
+    }
+}
+
+/// Returns whether `merge` should put the first node of `other` before the first node of `slot`,
+/// or `None` once `other` is empty. Ties go to whichever of the two came from `a`.
+fn takes_other<T, F: FnMut(&T, &T) -> Ordering>(
+    slot: &ListNode<T>,
+    other: &ListNode<T>,
+    compare: &mut F,
+    slot_from_b: bool,
+) -> Option<bool> {
+    match (slot, other) {
+        (_, ListNode::Nil) => None,
+        (ListNode::Nil, _) => Some(true),
+        (ListNode::Cons(x, _), ListNode::Cons(y, _)) => Some(match compare(y, x) {
+            Ordering::Less => true,
+            Ordering::Equal => slot_from_b,
+            Ordering::Greater => false,
+        }),
     }
 }
 
 // Implement `PartialEq` for `ListNode<T>`
 impl<T: PartialEq> PartialEq for ListNode<T> {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (ListNode::Nil, ListNode::Nil) => true,
-            (ListNode::Cons(j, next_j), ListNode::Cons(k, next_k)) => j == k && next_j == next_k,
-            _ => false,
-        }
+        self.iter().eq(other.iter())
     }
 }
 
 // Implement `Eq` for `ListNode<T>`
 impl<T: Eq> Eq for ListNode<T> {}
 
+/// Lists are ordered lexicographically, like `Vec<T>`
+impl<T: PartialOrd> PartialOrd for ListNode<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ListNode<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ListNode<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Prefixing the length keeps the lists `[[a], [b]]` and `[[a, b]]` from feeding the
+        // hasher the same sequence of values
+        self.len().hash(state);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
 // Implement `Display` for `ListNode<T>`
 impl<T: Display> Display for ListNode<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ListNode::Nil => write!(f, "Nil"),
-            ListNode::Cons(value, next) => {
-                write!(f, "{} -> {}", value, next)
+        for value in self.iter() {
+            write!(f, "{}{}", value, SEPARATOR)?;
+        }
+        write!(f, "{}", TERMINATOR)
+    }
+}
+
+impl<T: Clone> Clone for ListNode<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+/// Separator written between values by `Display for ListNode<T>`
+const SEPARATOR: &str = " -> ";
+/// Terminator written at the end of every list by `Display for ListNode<T>`
+const TERMINATOR: &str = "Nil";
+
+/// Parses the format written by `Display`, e.g. `1 -> 2 -> 3 -> Nil`.
+///
+/// Values whose own `Display` output contains `" -> "`, or is exactly `"Nil"`, can't be told apart
+/// from the list syntax and won't round-trip.
+impl<T: FromStr> FromStr for ListNode<T> {
+    type Err = ParseListError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut list = ListNode::Nil;
+        let mut tail = &mut list;
+        let mut position = 0;
+        while let Some(len) = s[position..].find(SEPARATOR) {
+            let value = s[position..position + len].parse().map_err(|e| {
+                ParseListError::new(position, ParseListErrorKind::InvalidElement(e))
+            })?;
+            tail = tail.insert(value);
+            position += len + SEPARATOR.len();
+        }
+        match &s[position..] {
+            TERMINATOR => Ok(list),
+            rest if rest.starts_with(TERMINATOR) => Err(ParseListError::new(
+                position + TERMINATOR.len(),
+                ParseListErrorKind::TrailingInput,
+            )),
+            _ => Err(ParseListError::new(
+                position,
+                ParseListErrorKind::MissingNil,
+            )),
+        }
+    }
+}
+
+/// The error returned when parsing a `ListNode<T>` from a string fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseListError<E> {
+    position: usize,
+    kind: ParseListErrorKind<E>,
+}
+
+/// The reason parsing a `ListNode<T>` failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseListErrorKind<E> {
+    /// The input does not end with `Nil`
+    MissingNil,
+    /// A value could not be parsed as `T`; holds the error from `T::from_str`
+    InvalidElement(E),
+    /// There is more input after the `Nil` terminator
+    TrailingInput,
+}
+
+impl<E> ParseListError<E> {
+    fn new(position: usize, kind: ParseListErrorKind<E>) -> Self {
+        ParseListError { position, kind }
+    }
+
+    /// Returns the byte offset in the input at which the error was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the reason parsing failed
+    pub fn kind(&self) -> &ParseListErrorKind<E> {
+        &self.kind
+    }
+}
+
+impl<E: Display> Display for ParseListError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseListErrorKind::MissingNil => {
+                write!(f, "expected `{}` at byte {}", TERMINATOR, self.position)
+            }
+            ParseListErrorKind::InvalidElement(e) => {
+                write!(f, "invalid element at byte {}: {}", self.position, e)
+            }
+            ParseListErrorKind::TrailingInput => {
+                write!(
+                    f,
+                    "unexpected input after `{}` at byte {}",
+                    TERMINATOR, self.position
+                )
             }
         }
     }
 }
 
+impl<E: Error + 'static> Error for ParseListError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ParseListErrorKind::InvalidElement(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 // Implement `From<Vec<T>>` for `ListNode<T>`
 impl<T> From<Vec<T>> for ListNode<T> {
     fn from(vec: Vec<T>) -> Self {
-        let mut list = ListNode::Nil;
-        for value in vec {
-            list.insert(value);
-        }
-        list
+        vec.into_iter().collect()
     }
 }
 
@@ -107,10 +689,399 @@ impl<T> From<Vec<T>> for ListNode<T> {
 impl<T> From<ListNode<T>> for Vec<T> {
     fn from(mut list: ListNode<T>) -> Self {
         let mut vec = Vec::new();
-        while let ListNode::Cons(value, next) = list {
+        while let Some((value, next)) = list.into_parts() {
             vec.push(value);
             list = *next;
         }
         vec
     }
 }
+
+/// A cursor over a `ListNode<T>` that can edit the list in place, created by
+/// [`ListNode::cursor_mut`].
+///
+/// The cursor always rests on a node of the list: either a `Cons` holding the current value, or the
+/// `Nil` terminator once it has moved past the last value.
+pub struct CursorMut<'a, T> {
+    // Always `Some`; the `Option` only lets `move_next` move the reference out temporarily.
+    node: Option<&'a mut ListNode<T>>,
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    fn node(&mut self) -> &mut ListNode<T> {
+        self.node
+            .as_deref_mut()
+            .expect("cursor always rests on a node")
+    }
+
+    /// Returns the position of the cursor, counted from the node it was created on
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns `true` if the cursor has moved past the last value of the list
+    pub fn is_end(&self) -> bool {
+        matches!(self.node, Some(ListNode::Nil))
+    }
+
+    /// Moves the cursor to the next node. Returns `false`, and leaves the cursor where it is, if
+    /// the cursor is already at the end of the list.
+    pub fn move_next(&mut self) -> bool {
+        match self.node.take() {
+            Some(ListNode::Cons(_, next)) => {
+                self.node = Some(next);
+                self.index += 1;
+                true
+            }
+            node => {
+                self.node = node;
+                false
+            }
+        }
+    }
+
+    /// Returns a reference to the value under the cursor, or `None` at the end of the list
+    pub fn peek(&self) -> Option<&T> {
+        match self.node.as_deref()? {
+            ListNode::Nil => None,
+            ListNode::Cons(value, _) => Some(value),
+        }
+    }
+
+    /// Returns a mutable reference to the value under the cursor, or `None` at the end of the list
+    pub fn current(&mut self) -> Option<&mut T> {
+        match self.node() {
+            ListNode::Nil => None,
+            ListNode::Cons(value, _) => Some(value),
+        }
+    }
+
+    /// Consumes the cursor and returns a mutable reference to the value under it, with the
+    /// lifetime of the list, or `None` at the end of the list
+    pub fn into_current(self) -> Option<&'a mut T> {
+        match self.node? {
+            ListNode::Nil => None,
+            ListNode::Cons(value, _) => Some(value),
+        }
+    }
+
+    /// Inserts `value` before the cursor. The cursor stays on the value it was on (or at the end of
+    /// the list).
+    pub fn insert_before(&mut self, value: T) {
+        let node = self.node();
+        let rest = mem::take(node);
+        *node = ListNode::Cons(value, Box::new(rest));
+        self.move_next();
+    }
+
+    /// Inserts `value` after the cursor. The cursor does not move; at the end of the list, this
+    /// appends `value` and leaves the cursor on it.
+    pub fn insert_after(&mut self, value: T) {
+        match self.node() {
+            ListNode::Cons(_, next) => {
+                let rest = mem::take(&mut **next);
+                **next = ListNode::Cons(value, Box::new(rest));
+            }
+            node @ ListNode::Nil => {
+                *node = ListNode::Cons(value, Box::new(ListNode::Nil));
+            }
+        }
+    }
+
+    /// Removes the value under the cursor and returns it, moving the cursor onto the next value.
+    /// Returns `None` at the end of the list.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.node();
+        let (value, next) = mem::take(node).into_parts()?;
+        *node = *next;
+        Some(value)
+    }
+
+    /// Inserts all values of `list` before the cursor. The cursor stays on the value it was on (or
+    /// at the end of the list).
+    pub fn splice_before(&mut self, list: ListNode<T>) {
+        let node = self.node();
+        let rest = mem::replace(node, list);
+        while self.move_next() {}
+        *self.node() = rest;
+    }
+
+    /// Splits the list at the cursor: the value under the cursor and everything after it are
+    /// removed and returned as a new list, leaving the cursor at the end of the shortened list.
+    pub fn split(&mut self) -> ListNode<T> {
+        mem::take(self.node())
+    }
+}
+
+/// Borrowing iterator over a `ListNode<T>`, created by [`ListNode::iter`]
+pub struct Iter<'a, T> {
+    next: &'a ListNode<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            ListNode::Nil => None,
+            ListNode::Cons(value, next) => {
+                self.next = next;
+                Some(value)
+            }
+        }
+    }
+}
+
+/// Mutably borrowing iterator over a `ListNode<T>`, created by [`ListNode::iter_mut`]
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut ListNode<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next.take()? {
+            ListNode::Nil => None,
+            ListNode::Cons(value, next) => {
+                self.next = Some(next);
+                Some(value)
+            }
+        }
+    }
+}
+
+/// Consuming iterator over a `ListNode<T>`, created by `into_iter`
+pub struct IntoIter<T> {
+    list: ListNode<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, next) = mem::take(&mut self.list).into_parts()?;
+        self.list = *next;
+        Some(value)
+    }
+}
+
+impl<T> IntoIterator for ListNode<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ListNode<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ListNode<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ListNode<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = ListNode::Nil;
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for ListNode<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        // Walk to the `Nil` terminator once, then keep appending after the node we just inserted
+        let mut tail = self;
+        while let ListNode::Cons(_, next) = tail {
+            tail = next;
+        }
+        for value in iter {
+            tail = tail.insert(value);
+        }
+    }
+}
+
+/// An owned `ListNode<T>` chain that caches its length and keeps the values pushed to its back in
+/// a buffer, so appending to either end takes amortized constant time
+pub struct LinkedList<T> {
+    // The front of the list. It is only `Nil` when the whole list is empty.
+    head: ListNode<T>,
+    // The values that follow `head`, oldest first. They are linked onto the end of `head` only when
+    // `head` runs out or the whole chain is needed, so each value is relinked at most once.
+    back: Vec<T>,
+    len: usize,
+}
+
+impl<T> LinkedList<T> {
+    /// Creates a new empty list
+    pub fn new() -> Self {
+        ListNode::Nil.into()
+    }
+
+    /// Returns the number of values in the list
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list contains no values
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `value` to the back of the list
+    pub fn push_back(&mut self, value: T) {
+        if self.is_empty() {
+            self.head.push_front(value);
+        } else {
+            self.back.push(value);
+        }
+        self.len += 1;
+    }
+
+    /// Prepends `value` to the front of the list
+    pub fn push_front(&mut self, value: T) {
+        self.head.push_front(value);
+        self.len += 1;
+    }
+
+    /// Removes the first value of the list and returns it, or `None` if the list is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        let value = self.head.pop_front()?;
+        self.len -= 1;
+        if let ListNode::Nil = self.head {
+            self.head = mem::take(&mut self.back).into();
+        }
+        Some(value)
+    }
+
+    /// Returns a reference to the first value of the list, or `None` if the list is empty
+    pub fn front(&self) -> Option<&T> {
+        self.head.peek()
+    }
+
+    /// Links the buffered back of the list onto the chain and returns the whole chain. This walks
+    /// the chain once if values were pushed to the back since the last call.
+    pub fn as_list(&mut self) -> &ListNode<T> {
+        if !self.back.is_empty() {
+            self.head.extend(self.back.drain(..));
+        }
+        &self.head
+    }
+
+    /// Returns an iterator over references to the values in the list
+    pub fn iter(&self) -> Chain<Iter<'_, T>, slice::Iter<'_, T>> {
+        self.head.iter().chain(self.back.iter())
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display> Display for LinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for value in self.iter() {
+            write!(f, "{}{}", value, SEPARATOR)?;
+        }
+        write!(f, "{}", TERMINATOR)
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> From<ListNode<T>> for LinkedList<T> {
+    fn from(list: ListNode<T>) -> Self {
+        LinkedList {
+            len: list.len(),
+            head: list,
+            back: Vec::new(),
+        }
+    }
+}
+
+impl<T> From<LinkedList<T>> for ListNode<T> {
+    fn from(mut list: LinkedList<T>) -> Self {
+        list.as_list();
+        list.head
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for Vec<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        ListNode::from(list).into()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ListNode::from(self).into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Chain<Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,255 @@
+use std::{fmt::Display, iter, ops::Deref, rc::Rc, sync::Arc};
+
+use crate::list::ListNode;
+
+/// The kind of reference-counted pointer a persistent structure uses to share its nodes
+pub trait PointerKind {
+    type Pointer<U>: Clone + Deref<Target = U>;
+
+    /// Moves `value` into a new shared pointer
+    fn new<U>(value: U) -> Self::Pointer<U>;
+
+    /// Returns the pointee if `pointer` is its only owner, or gives `pointer` back otherwise
+    fn try_unwrap<U>(pointer: Self::Pointer<U>) -> Result<U, Self::Pointer<U>>;
+
+    /// Returns `true` if both pointers point at the same allocation
+    fn ptr_eq<U>(a: &Self::Pointer<U>, b: &Self::Pointer<U>) -> bool;
+
+    /// Returns a mutable reference to the pointee, first cloning it into a new allocation if
+    /// other pointers share it
+    fn make_mut<U: Clone>(pointer: &mut Self::Pointer<U>) -> &mut U;
+}
+
+/// Shares nodes through `Rc`; cheapest, but confined to one thread
+pub struct RcK;
+
+/// Shares nodes through `Arc`, so versions can be sent between threads
+pub struct ArcK;
+
+impl PointerKind for RcK {
+    type Pointer<U> = Rc<U>;
+
+    fn new<U>(value: U) -> Rc<U> {
+        Rc::new(value)
+    }
+
+    fn try_unwrap<U>(pointer: Rc<U>) -> Result<U, Rc<U>> {
+        Rc::try_unwrap(pointer)
+    }
+
+    fn ptr_eq<U>(a: &Rc<U>, b: &Rc<U>) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+
+    fn make_mut<U: Clone>(pointer: &mut Rc<U>) -> &mut U {
+        Rc::make_mut(pointer)
+    }
+}
+
+impl PointerKind for ArcK {
+    type Pointer<U> = Arc<U>;
+
+    fn new<U>(value: U) -> Arc<U> {
+        Arc::new(value)
+    }
+
+    fn try_unwrap<U>(pointer: Arc<U>) -> Result<U, Arc<U>> {
+        Arc::try_unwrap(pointer)
+    }
+
+    fn ptr_eq<U>(a: &Arc<U>, b: &Arc<U>) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+
+    fn make_mut<U: Clone>(pointer: &mut Arc<U>) -> &mut U {
+        Arc::make_mut(pointer)
+    }
+}
+
+/// An immutable singly linked list whose versions share their common suffixes.
+///
+/// Every operation leaves `self` untouched and returns a new version in O(1); cloning a version
+/// only bumps a reference count.
+pub struct PersistentList<T, P: PointerKind = RcK> {
+    head: Option<P::Pointer<Node<T, P>>>,
+}
+
+/// A `PersistentList<T>` whose versions can be shared between threads
+pub type ArcList<T> = PersistentList<T, ArcK>;
+
+struct Node<T, P: PointerKind> {
+    value: T,
+    len: usize,
+    next: PersistentList<T, P>,
+}
+
+impl<T, P: PointerKind> PersistentList<T, P> {
+    /// Creates a new empty list
+    pub fn new() -> Self {
+        PersistentList { head: None }
+    }
+
+    /// Returns a new version of the list with `value` in front of the values of `self`
+    pub fn cons(&self, value: T) -> Self {
+        PersistentList {
+            head: Some(P::new(Node {
+                value,
+                len: self.len() + 1,
+                next: self.clone(),
+            })),
+        }
+    }
+
+    /// Returns a reference to the first value of the list, or `None` if the list is empty
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_deref().map(|node| &node.value)
+    }
+
+    /// Returns the list without its first value, or `None` if the list is empty
+    pub fn tail(&self) -> Option<Self> {
+        self.head.as_deref().map(|node| node.next.clone())
+    }
+
+    /// Returns the number of values in the list
+    pub fn len(&self) -> usize {
+        self.head.as_deref().map_or(0, |node| node.len)
+    }
+
+    /// Returns `true` if the list contains no values
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns `true` if both lists are the same version, i.e. they share their first node
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (None, None) => true,
+            (Some(a), Some(b)) => P::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns an iterator over references to the values in the list
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter { next: self }
+    }
+}
+
+impl<T, P: PointerKind> Clone for PersistentList<T, P> {
+    fn clone(&self) -> Self {
+        PersistentList {
+            head: self.head.clone(),
+        }
+    }
+}
+
+impl<T, P: PointerKind> Default for PersistentList<T, P> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+impl<T, P: PointerKind> Drop for PersistentList<T, P> {
+    fn drop(&mut self) {
+        // Release nodes one at a time until we reach one that another version still shares
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            head = match P::try_unwrap(node) {
+                Ok(mut node) => node.next.head.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<T: PartialEq, P: PointerKind> PartialEq for PersistentList<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, P: PointerKind> Eq for PersistentList<T, P> {}
+
+impl<T: std::fmt::Debug, P: PointerKind> std::fmt::Debug for PersistentList<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Display, P: PointerKind> Display for PersistentList<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for value in self.iter() {
+            write!(f, "{} -> ", value)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
+impl<T, P: PointerKind> FromIterator<T> for PersistentList<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values: ListNode<T> = iter.into_iter().collect();
+        values.reverse();
+        values
+            .into_iter()
+            .fold(PersistentList::new(), |list, value| list.cons(value))
+    }
+}
+
+impl<T, P: PointerKind> From<ListNode<T>> for PersistentList<T, P> {
+    fn from(list: ListNode<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T: Clone, P: PointerKind> From<&PersistentList<T, P>> for ListNode<T> {
+    fn from(list: &PersistentList<T, P>) -> Self {
+        list.iter().cloned().collect()
+    }
+}
+
+impl<T: Clone, P: PointerKind> From<PersistentList<T, P>> for ListNode<T> {
+    /// Moves values out of nodes that no other version shares and clones the rest
+    fn from(mut list: PersistentList<T, P>) -> Self {
+        let mut values = ListNode::new();
+        let mut tail = &mut values;
+        let mut head = list.head.take();
+        while let Some(node) = head {
+            head = match P::try_unwrap(node) {
+                Ok(mut node) => {
+                    tail = tail.insert(node.value);
+                    node.next.head.take()
+                }
+                Err(shared) => {
+                    let rest = iter::once(&shared.value).chain(shared.next.iter());
+                    tail.extend(rest.cloned());
+                    None
+                }
+            };
+        }
+        values
+    }
+}
+
+/// Borrowing iterator over a `PersistentList<T>`, created by [`PersistentList::iter`]
+pub struct Iter<'a, T, P: PointerKind> {
+    next: &'a PersistentList<T, P>,
+}
+
+impl<'a, T, P: PointerKind> Iterator for Iter<'a, T, P> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.head.as_deref()?;
+        self.next = &node.next;
+        Some(&node.value)
+    }
+}
+
+impl<'a, T, P: PointerKind> IntoIterator for &'a PersistentList<T, P> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::Display;
+
+use crate::{
+    augment::Count,
+    avl_core::{self, Avl},
+    persistent_list::{ArcK, PointerKind, RcK},
+    print,
+};
+
+/// An immutable AVL tree whose versions share their untouched subtrees.
+///
+/// `insert` and `remove` leave `self` untouched and return a new version, copying only the O(log n)
+/// nodes on the path they change and the nodes the rebalancing rotates; cloning a version only
+/// bumps a reference count.
+pub struct PersistentTree<T: Ord, P: PointerKind = RcK>(Avl<T, Count, P>);
+
+/// A `PersistentTree<T>` whose versions can be shared between threads
+pub type ArcTree<T> = PersistentTree<T, ArcK>;
+
+impl<T: Ord, P: PointerKind> PersistentTree<T, P> {
+    /// Creates a new empty tree
+    pub fn new() -> Self {
+        PersistentTree(Avl::new())
+    }
+
+    /// Returns the number of values in the tree
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the height of the tree
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Returns `true` if the tree contains `value`
+    pub fn contains(&self, value: &T) -> bool {
+        self.0.find(|entry| value.cmp(entry)).is_some()
+    }
+
+    /// Returns `true` if both trees are the same version, i.e. they share their root node
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+
+    /// Returns an iterator over references to the values of the tree, in ascending order
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter {
+            iter: self.0.iter(),
+        }
+    }
+
+    /// Verifies that the tree is a valid balanced binary search tree whose cached heights and
+    /// sizes are correct
+    pub fn validate(&self) -> bool {
+        self.0.validate_by(T::cmp)
+    }
+
+    /// Draws the tree level by level, like [`TreeNode::show`](crate::tree::TreeNode::show)
+    pub fn show(&self, width: usize) -> String
+    where
+        T: Display,
+    {
+        print::show(&self.0, width)
+    }
+}
+
+// Versioned updates. Nodes are copied just before they change, so a node that only this version
+// owns is updated in place and a shared one is copied first.
+impl<T: Ord + Clone, P: PointerKind> PersistentTree<T, P> {
+    /// Returns a new version of the tree with `value` inserted. If the value already exists in
+    /// the tree, the new version shares the whole tree.
+    ///
+    /// The new version is rebalanced if necessary
+    pub fn insert(&self, value: T) -> Self {
+        let mut tree = self.clone();
+        // Checking first keeps the search from copying the path to a value that is already there
+        if !self.contains(&value) {
+            let _ = tree.0.insert_by(value, &T::cmp);
+        }
+        tree
+    }
+
+    /// Returns a new version of the tree with `value` removed. If the value is not in the tree,
+    /// the new version shares the whole tree.
+    ///
+    /// The new version is rebalanced if necessary
+    pub fn remove(&self, value: &T) -> Self {
+        let mut tree = self.clone();
+        if self.contains(value) {
+            tree.0.remove_by(&|entry| value.cmp(entry));
+        }
+        tree
+    }
+}
+
+impl<T: Ord, P: PointerKind> Clone for PersistentTree<T, P> {
+    fn clone(&self) -> Self {
+        PersistentTree(self.0.share())
+    }
+}
+
+impl<T: Ord, P: PointerKind> Default for PersistentTree<T, P> {
+    fn default() -> Self {
+        PersistentTree::new()
+    }
+}
+
+impl<T: Ord, P: PointerKind> PartialEq for PersistentTree<T, P> {
+    /// Two versions are equal if they hold the same values, whatever the shape of their trees
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Ord, P: PointerKind> Eq for PersistentTree<T, P> {}
+
+impl<T: Ord + std::fmt::Debug, P: PointerKind> std::fmt::Debug for PersistentTree<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord + Clone, P: PointerKind> FromIterator<T> for PersistentTree<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = PersistentTree::new();
+        for value in iter {
+            // The tree is not shared yet, so every node is updated in place
+            let _ = tree.0.insert_by(value, &T::cmp);
+        }
+        tree
+    }
+}
+
+impl<T: Ord + Clone, P: PointerKind> From<Vec<T>> for PersistentTree<T, P> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+/// Borrowing iterator over a `PersistentTree<T>`, created by [`PersistentTree::iter`]
+pub struct Iter<'a, T: Ord, P: PointerKind> {
+    iter: avl_core::Iter<'a, T, Count, P>,
+}
+
+impl<'a, T: Ord, P: PointerKind> Iterator for Iter<'a, T, P> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'a, T: Ord, P: PointerKind> IntoIterator for &'a PersistentTree<T, P> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
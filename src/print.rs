@@ -1,7 +1,7 @@
 use std::fmt::{Display, Formatter};
-use crate::tree::TreeNode;
+use crate::tree::{Shape, TreeNode};
 
-fn intersperse<T: Clone>(v: &Vec<T>, sep: T, count: usize) -> Vec<T> {
+fn intersperse<T: Clone>(v: &[T], sep: T, count: usize) -> Vec<T> {
     if v.is_empty() {
         Vec::new()
     } else {
@@ -18,69 +18,77 @@ fn intersperse<T: Clone>(v: &Vec<T>, sep: T, count: usize) -> Vec<T> {
     }
 }
 
-impl<T: Ord + Clone + Display> TreeNode<T> {
-    fn rows(&self) -> Vec<Vec<Option<T>>> {
-        let mut rows: Vec<Vec<Option<T>>> = Vec::new();
-        let mut current: Vec<TreeNode<T>> = vec![self.clone()];
-        let mut next: Vec<TreeNode<T>> = vec![];
-        loop {
-            let mut row = vec![];
-            for elt in current.iter() {
-                match elt {
-                    TreeNode::Leaf => row.push(None),
-                    TreeNode::Node(value, _, _) => row.push(Some(value.clone())),
-                }
-            }
-            if row.iter().all(|x| x.is_none()) {
-                break;
-            }
-            rows.push(row);
+/// Collects the values of `tree` level by level, with `None` standing in for missing nodes so
+/// that every level is twice as long as the one above it
+fn rows<S: Shape>(tree: &S) -> Vec<Vec<Option<&S::Value>>> {
+    let mut rows: Vec<Vec<Option<&S::Value>>> = Vec::new();
+    let mut current: Vec<Option<&S>> = vec![Some(tree)];
+    let mut next: Vec<Option<&S>> = vec![];
+    loop {
+        let row: Vec<Option<&S::Value>> = current
+            .iter()
+            .map(|elt| elt.and_then(|tree| tree.parts()).map(|(_, value, _)| value))
+            .collect();
+        if row.iter().all(|x| x.is_none()) {
+            break;
+        }
+        rows.push(row);
 
-            for value in current {
-                match value {
-                    TreeNode::Leaf => {
-                        next.push(TreeNode::Leaf);
-                        next.push(TreeNode::Leaf);
-                    }
-                    TreeNode::Node(_, left, right) => {
-                        next.push(*left);
-                        next.push(*right);
-                    }
+        for elt in current {
+            match elt.and_then(|tree| tree.parts()) {
+                None => {
+                    next.push(None);
+                    next.push(None);
+                }
+                Some((left, _, right)) => {
+                    next.push(Some(left));
+                    next.push(Some(right));
                 }
             }
-            current = next;
-            next = vec![];
         }
-        rows
+        current = next;
+        next = vec![];
     }
-    pub fn show(&self, width: usize) -> String {
-        let rows = self.rows();
+    rows
+}
 
-        let mut with_padding = vec![];
-        let mut around_padding_count = 0;
-        let mut inter_padding_count = 1;
-        for row in rows.into_iter().rev() {
-            let around_padding = vec![None; around_padding_count];
-            with_padding.push(
-                around_padding
-                    .clone()
-                    .into_iter()
-                    .chain(intersperse(&row, None, inter_padding_count).into_iter())
-                    .chain(around_padding)
-                    .collect::<Vec<_>>(),
-            );
-            around_padding_count = inter_padding_count;
-            inter_padding_count = inter_padding_count * 2 + 1;
-        }
+/// Draws `tree` level by level, centring each value in a cell `width` characters wide
+pub(crate) fn show<S: Shape>(tree: &S, width: usize) -> String
+where
+    S::Value: Display,
+{
+    let rows = rows(tree);
 
-        with_padding.into_iter().rev().map(|row| {
-            row.into_iter().map(|x| {
-                match x {
-                    Some(v) => format!("{: ^width$}", v, width = width),
-                    None => format!("{: ^width$}", "", width = width),
-                }
-            }).collect::<Vec<_>>().join("")
-        }).collect::<Vec<_>>().join("\n")
+    let mut with_padding = vec![];
+    let mut around_padding_count = 0;
+    let mut inter_padding_count = 1;
+    for row in rows.into_iter().rev() {
+        let around_padding = vec![None; around_padding_count];
+        with_padding.push(
+            around_padding
+                .clone()
+                .into_iter()
+                .chain(intersperse(&row, None, inter_padding_count))
+                .chain(around_padding)
+                .collect::<Vec<_>>(),
+        );
+        around_padding_count = inter_padding_count;
+        inter_padding_count = inter_padding_count * 2 + 1;
+    }
+
+    with_padding.into_iter().rev().map(|row| {
+        row.into_iter().map(|x| {
+            match x {
+                Some(v) => format!("{: ^width$}", v, width = width),
+                None => format!("{: ^width$}", "", width = width),
+            }
+        }).collect::<Vec<_>>().join("")
+    }).collect::<Vec<_>>().join("\n")
+}
+
+impl<T: Ord + Clone + Display> TreeNode<T> {
+    pub fn show(&self, width: usize) -> String {
+        show(self, width)
     }
 }
 
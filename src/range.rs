@@ -0,0 +1,175 @@
+use std::{
+    borrow::Borrow,
+    ops::{Bound, RangeBounds},
+    ptr,
+};
+
+use crate::tree::{Rebalance, TreeNode};
+
+// Range queries on `TreeNode<T>`
+impl<T: Ord> TreeNode<T> {
+    /// Returns a double-ended iterator over the values of the tree that lie in `range`, in
+    /// ascending order. Only the subtrees that overlap `range` are visited.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, T>
+    where
+        Q: Ord + ?Sized,
+        T: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        let mut front = Vec::new();
+        let mut node = self;
+        while let TreeNode::Node(value, left, right) = node {
+            if after_start(range.start_bound(), value.borrow()) {
+                front.push((value, &**right));
+                node = left;
+            } else {
+                node = right;
+            }
+        }
+
+        let mut back = Vec::new();
+        let mut node = self;
+        while let TreeNode::Node(value, left, right) = node {
+            if before_end(range.end_bound(), value.borrow()) {
+                back.push((value, &**left));
+                node = right;
+            } else {
+                node = left;
+            }
+        }
+
+        // The range is empty if one end has nothing to give or the ends have already crossed
+        let done = match (front.last(), back.last()) {
+            (Some((first, _)), Some((last, _))) => first > last,
+            _ => true,
+        };
+        Range { front, back, done }
+    }
+
+    /// Returns the number of values of the tree that lie in `range`
+    pub fn range_count<Q, R>(&self, range: R) -> usize
+    where
+        Q: Ord + ?Sized,
+        T: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        self.range(range).count()
+    }
+
+    /// Removes all values that lie in `range` from the tree and returns them in ascending order.
+    ///
+    /// After each removal, the tree is rebalanced if necessary
+    pub fn remove_range<Q, R>(&mut self, range: R) -> Vec<T>
+    where
+        Q: Ord + ?Sized,
+        T: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        let mut removed = Vec::new();
+        while let Some(value) = self.pop_first_in(&range) {
+            removed.push(value);
+        }
+        removed
+    }
+
+    /// Removes the smallest value that lies in `range` and returns it, or `None` if there is none
+    fn pop_first_in<Q, R>(&mut self, range: &R) -> Option<T>
+    where
+        Q: Ord + ?Sized,
+        T: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        let removed = match self {
+            TreeNode::Leaf => return None,
+            TreeNode::Node(value, left, right) => {
+                if !after_start(range.start_bound(), (*value).borrow()) {
+                    right.pop_first_in(range)
+                } else if let Some(first) = left.pop_first_in(range) {
+                    Some(first)
+                } else if before_end(range.end_bound(), (*value).borrow()) {
+                    Some(self.remove_root())
+                } else {
+                    None
+                }
+            }
+        };
+        if removed.is_some() {
+            self.rebalance();
+        }
+        removed
+    }
+}
+
+/// Returns `true` if `value` is not below the start bound of a range
+pub(crate) fn after_start<Q: Ord + ?Sized>(start: Bound<&Q>, value: &Q) -> bool {
+    match start {
+        Bound::Included(start) => value >= start,
+        Bound::Excluded(start) => value > start,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Returns `true` if `value` is not above the end bound of a range
+pub(crate) fn before_end<Q: Ord + ?Sized>(end: Bound<&Q>, value: &Q) -> bool {
+    match end {
+        Bound::Included(end) => value <= end,
+        Bound::Excluded(end) => value < end,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Double-ended iterator over the values of a `TreeNode<T>` in a range, created by
+/// [`TreeNode::range`]
+pub struct Range<'a, T: Ord> {
+    // Values still to be visited from the front, each with the right subtree to descend into
+    // after it. The top of the stack is the next value.
+    front: Vec<(&'a T, &'a TreeNode<T>)>,
+    // Same as `front`, mirrored: values from the back, each with its left subtree
+    back: Vec<(&'a T, &'a TreeNode<T>)>,
+    // Set once the two ends have met
+    done: bool,
+}
+
+impl<'a, T: Ord> Range<'a, T> {
+    /// Marks the iterator as done if `value`, just taken from one end, is the next value at the
+    /// other end
+    fn check_met(&mut self, value: &T, other_end_next: Option<&T>) {
+        if other_end_next.is_some_and(|next| ptr::eq(next, value)) {
+            self.done = true;
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (value, mut node) = self.front.pop()?;
+        while let TreeNode::Node(v, left, right) = node {
+            self.front.push((v, right));
+            node = left;
+        }
+        let back_next = self.back.last().map(|(v, _)| *v);
+        self.check_met(value, back_next);
+        Some(value)
+    }
+}
+
+impl<'a, T: Ord> DoubleEndedIterator for Range<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (value, mut node) = self.back.pop()?;
+        while let TreeNode::Node(v, left, right) = node {
+            self.back.push((v, left));
+            node = right;
+        }
+        let front_next = self.front.last().map(|(v, _)| *v);
+        self.check_met(value, front_next);
+        Some(value)
+    }
+}
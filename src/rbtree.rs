@@ -0,0 +1,336 @@
+use std::{cmp::Ordering, mem};
+
+use crate::sorted_set::SortedSet;
+
+/// A left-leaning red-black tree.
+///
+/// Every node is red or black. The root is black, a red node is always the left child of a black
+/// one, and every path from the root down to an empty subtree passes the same number of black
+/// nodes. Together these keep the height below 2 log2(n + 1), with fewer rotations per update
+/// than an AVL tree but a less strict balance.
+#[derive(Clone, Debug)]
+pub struct RedBlackTree<T: Ord> {
+    root: Link<T>,
+}
+
+type Link<T> = Option<Box<RbNode<T>>>;
+
+#[derive(Clone, Debug)]
+struct RbNode<T: Ord> {
+    value: T,
+    red: bool,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Ord> RbNode<T> {
+    fn new(value: T) -> Self {
+        RbNode {
+            value,
+            red: true,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Returns `true` if `link` is a red node; empty subtrees count as black
+fn is_red<T: Ord>(link: &Link<T>) -> bool {
+    link.as_ref().is_some_and(|node| node.red)
+}
+
+/// Returns `true` if the left child of `link` is a red node
+fn is_left_red<T: Ord>(link: &Link<T>) -> bool {
+    link.as_ref().is_some_and(|node| is_red(&node.left))
+}
+
+/// Turns the red right link of `node` into a red left link
+fn rotate_left<T: Ord>(mut node: Box<RbNode<T>>) -> Box<RbNode<T>> {
+    let mut pivot = node
+        .right
+        .take()
+        .expect("cannot rotate left without a right child");
+    node.right = pivot.left.take();
+    pivot.red = node.red;
+    node.red = true;
+    pivot.left = Some(node);
+    pivot
+}
+
+/// Turns the red left link of `node` into a red right link
+fn rotate_right<T: Ord>(mut node: Box<RbNode<T>>) -> Box<RbNode<T>> {
+    let mut pivot = node
+        .left
+        .take()
+        .expect("cannot rotate right without a left child");
+    node.left = pivot.right.take();
+    pivot.red = node.red;
+    node.red = true;
+    pivot.right = Some(node);
+    pivot
+}
+
+/// Flips the colours of `node` and its children, splitting or merging a 4-node
+fn flip_colors<T: Ord>(node: &mut RbNode<T>) {
+    node.red = !node.red;
+    for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+        child.red = !child.red;
+    }
+}
+
+/// Restores the left-leaning invariants at `node` on the way back up from an update
+fn fix_up<T: Ord>(mut node: Box<RbNode<T>>) -> Box<RbNode<T>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        node = rotate_left(node);
+    }
+    if is_red(&node.left) && is_left_red(&node.left) {
+        node = rotate_right(node);
+    }
+    if is_red(&node.left) && is_red(&node.right) {
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Makes the left child of `node` or one of its children red, so a value can be removed from the
+/// left subtree without leaving a path one black node short
+fn move_red_left<T: Ord>(mut node: Box<RbNode<T>>) -> Box<RbNode<T>> {
+    flip_colors(&mut node);
+    if is_left_red(&node.right) {
+        node.right = node.right.take().map(rotate_right);
+        node = rotate_left(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Makes the right child of `node` or one of its children red, so a value can be removed from
+/// the right subtree without leaving a path one black node short
+fn move_red_right<T: Ord>(mut node: Box<RbNode<T>>) -> Box<RbNode<T>> {
+    flip_colors(&mut node);
+    if is_left_red(&node.left) {
+        node = rotate_right(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Inserts `value` below `link` and returns the new subtree
+fn insert<T: Ord>(link: Link<T>, value: T) -> Box<RbNode<T>> {
+    let mut node = match link {
+        None => return Box::new(RbNode::new(value)),
+        Some(node) => node,
+    };
+    match value.cmp(&node.value) {
+        Ordering::Less => node.left = Some(insert(node.left.take(), value)),
+        Ordering::Greater => node.right = Some(insert(node.right.take(), value)),
+        Ordering::Equal => return node,
+    }
+    fix_up(node)
+}
+
+/// Removes the smallest value below `node` and returns it with the new subtree
+fn remove_min<T: Ord>(mut node: Box<RbNode<T>>) -> (Link<T>, T) {
+    if node.left.is_none() {
+        // A node without a left child has no right child either, as it would have to be red
+        return (None, node.value);
+    }
+    if !is_red(&node.left) && !is_left_red(&node.left) {
+        node = move_red_left(node);
+    }
+    let left = node
+        .left
+        .take()
+        .expect("the smallest value is in the left subtree");
+    let (left, min) = remove_min(left);
+    node.left = left;
+    (Some(fix_up(node)), min)
+}
+
+/// Removes `value`, which must be in the subtree, from below `node` and returns it with the new
+/// subtree
+fn remove<T: Ord>(mut node: Box<RbNode<T>>, value: &T) -> (Link<T>, T) {
+    if *value < node.value {
+        if !is_red(&node.left) && !is_left_red(&node.left) {
+            node = move_red_left(node);
+        }
+        let left = node.left.take().expect("the value is in the left subtree");
+        let (left, removed) = remove(left, value);
+        node.left = left;
+        return (Some(fix_up(node)), removed);
+    }
+    if is_red(&node.left) {
+        node = rotate_right(node);
+    }
+    if *value == node.value && node.right.is_none() {
+        return (None, node.value);
+    }
+    if !is_red(&node.right) && !is_left_red(&node.right) {
+        node = move_red_right(node);
+    }
+    let right = node
+        .right
+        .take()
+        .expect("the value is in the right subtree");
+    let (right, removed) = if *value == node.value {
+        // Replace the value with its in-order successor
+        let (right, successor) = remove_min(right);
+        (right, mem::replace(&mut node.value, successor))
+    } else {
+        remove(right, value)
+    };
+    node.right = right;
+    (Some(fix_up(node)), removed)
+}
+
+impl<T: Ord> RedBlackTree<T> {
+    /// Creates a new empty tree
+    pub fn new() -> Self {
+        RedBlackTree { root: None }
+    }
+
+    /// Returns `true` if the tree contains no values
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts `value` into the tree. If the value already exists in the tree, the function does
+    /// nothing.
+    ///
+    /// After insertion, the tree is recoloured and rotated if necessary
+    pub fn insert(&mut self, value: T) {
+        let mut root = insert(self.root.take(), value);
+        root.red = false;
+        self.root = Some(root);
+    }
+
+    /// Returns `true` if the tree contains `value`
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Removes `value` from the tree and returns it, or `None` if the value is not in the tree.
+    ///
+    /// After removal, the tree is recoloured and rotated if necessary
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        if !self.contains(value) {
+            return None;
+        }
+        let mut root = self.root.take()?;
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.red = true;
+        }
+        let (root, removed) = remove(root, value);
+        self.root = root.map(|mut root| {
+            root.red = false;
+            root
+        });
+        Some(removed)
+    }
+
+    /// Returns the height of the tree
+    pub fn height(&self) -> usize {
+        fn height<T: Ord>(link: &Link<T>) -> usize {
+            link.as_ref()
+                .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+
+    /// Returns an iterator over references to the values of the tree, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        let mut link = &self.root;
+        std::iter::from_fn(move || {
+            while let Some(node) = link {
+                stack.push(node);
+                link = &node.left;
+            }
+            let node = stack.pop()?;
+            link = &node.right;
+            Some(&node.value)
+        })
+    }
+
+    /// Verifies that the tree is a binary search tree whose colouring satisfies the left-leaning
+    /// red-black invariants
+    pub fn validate(&self) -> bool {
+        /// Returns the number of black nodes on every path down from `link` if the subtree is
+        /// valid and all its values lie between `min` and `max`
+        fn check<T: Ord>(link: &Link<T>, min: Option<&T>, max: Option<&T>) -> Option<usize> {
+            let node = match link {
+                None => return Some(0),
+                Some(node) => node,
+            };
+            if min.is_some_and(|min| node.value <= *min)
+                || max.is_some_and(|max| node.value >= *max)
+            {
+                return None;
+            }
+            if is_red(&node.right) || (node.red && is_red(&node.left)) {
+                return None;
+            }
+            let left = check(&node.left, min, Some(&node.value))?;
+            let right = check(&node.right, Some(&node.value), max)?;
+            (left == right).then_some(left + !node.red as usize)
+        }
+        !is_red(&self.root) && check(&self.root, None, None).is_some()
+    }
+}
+
+impl<T: Ord> Default for RedBlackTree<T> {
+    fn default() -> Self {
+        RedBlackTree::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RedBlackTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = RedBlackTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+impl<T: Ord> SortedSet<T> for RedBlackTree<T> {
+    fn new() -> Self {
+        RedBlackTree::new()
+    }
+
+    fn insert(&mut self, value: T) {
+        RedBlackTree::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        RedBlackTree::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        RedBlackTree::remove(self, value)
+    }
+
+    fn validate(&self) -> bool {
+        RedBlackTree::validate(self)
+    }
+
+    fn height(&self) -> usize {
+        RedBlackTree::height(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        RedBlackTree::iter(self)
+    }
+}
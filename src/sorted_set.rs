@@ -0,0 +1,97 @@
+use crate::{avl::AvlTree, tree::TreeNode};
+
+/// The interface shared by the balanced search trees in this crate that store a set of values,
+/// so that they can be tested and benchmarked against each other.
+///
+/// Inserting a value that is already in the set leaves the set unchanged.
+pub trait SortedSet<T: Ord>: Sized {
+    /// Creates a new empty set
+    fn new() -> Self;
+
+    /// Inserts `value` into the set, rebalancing if necessary
+    fn insert(&mut self, value: T);
+
+    /// Returns `true` if the set contains `value`
+    fn contains(&self, value: &T) -> bool;
+
+    /// Removes `value` from the set and returns it, or `None` if the value is not in the set
+    fn remove(&mut self, value: &T) -> Option<T>;
+
+    /// Verifies that the tree is a valid search tree that satisfies the invariants of its
+    /// balancing scheme
+    fn validate(&self) -> bool;
+
+    /// Returns the height of the tree
+    fn height(&self) -> usize;
+
+    /// Returns an iterator over references to the values of the set, in ascending order
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
+}
+
+impl<T: Ord> SortedSet<T> for TreeNode<T> {
+    fn new() -> Self {
+        TreeNode::new()
+    }
+
+    fn insert(&mut self, value: T) {
+        TreeNode::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        TreeNode::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        TreeNode::remove(self, value)
+    }
+
+    fn validate(&self) -> bool {
+        TreeNode::validate(self)
+    }
+
+    fn height(&self) -> usize {
+        TreeNode::height(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        TreeNode::iter(self)
+    }
+}
+
+impl<T: Ord> SortedSet<T> for AvlTree<T> {
+    fn new() -> Self {
+        AvlTree::new()
+    }
+
+    fn insert(&mut self, value: T) {
+        AvlTree::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        AvlTree::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        AvlTree::remove(self, value)
+    }
+
+    fn validate(&self) -> bool {
+        AvlTree::validate(self)
+    }
+
+    fn height(&self) -> usize {
+        AvlTree::height(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        AvlTree::iter(self)
+    }
+}
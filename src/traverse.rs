@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+
+use crate::{range::Range, tree::TreeNode};
+
+// Traversals of `TreeNode<T>`. Each iterator keeps its own stack or queue of pending subtrees
+// instead of recursing, so walking a tree never grows the call stack.
+impl<T: Ord> TreeNode<T> {
+    /// Returns a double-ended iterator over references to the values of the tree, in ascending
+    /// order
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            range: self.range::<T, _>(..),
+        }
+    }
+
+    /// Returns an iterator over references to the values of the tree in pre-order: each value
+    /// comes before the values of its left subtree, which come before those of its right subtree
+    pub fn preorder(&self) -> Preorder<'_, T> {
+        Preorder { stack: vec![self] }
+    }
+
+    /// Returns an iterator over references to the values of the tree in post-order: each value
+    /// comes after the values of its left and right subtrees
+    pub fn postorder(&self) -> Postorder<'_, T> {
+        Postorder {
+            stack: vec![(self, false)],
+        }
+    }
+
+    /// Returns an iterator over references to the values of the tree in breadth-first order:
+    /// level by level from the root, and from left to right within a level
+    pub fn level_order(&self) -> LevelOrder<'_, T> {
+        LevelOrder {
+            queue: VecDeque::from([self]),
+        }
+    }
+}
+
+/// Borrowing in-order iterator over a `TreeNode<T>`, created by [`TreeNode::iter`]
+pub struct Iter<'a, T: Ord> {
+    range: Range<'a, T>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next()
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back()
+    }
+}
+
+/// Borrowing pre-order iterator over a `TreeNode<T>`, created by [`TreeNode::preorder`]
+pub struct Preorder<'a, T: Ord> {
+    // Subtrees still to be visited; the top of the stack is visited next
+    stack: Vec<&'a TreeNode<T>>,
+}
+
+impl<'a, T: Ord> Iterator for Preorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let TreeNode::Node(value, left, right) = self.stack.pop()? {
+                self.stack.push(right);
+                self.stack.push(left);
+                return Some(value);
+            }
+        }
+    }
+}
+
+/// Borrowing post-order iterator over a `TreeNode<T>`, created by [`TreeNode::postorder`]
+pub struct Postorder<'a, T: Ord> {
+    // Subtrees still to be visited, each flagged with whether its children have been pushed
+    // already, in which case its own value is next
+    stack: Vec<(&'a TreeNode<T>, bool)>,
+}
+
+impl<'a, T: Ord> Iterator for Postorder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if let TreeNode::Node(value, left, right) = node {
+                if expanded {
+                    return Some(value);
+                }
+                self.stack.push((node, true));
+                self.stack.push((right, false));
+                self.stack.push((left, false));
+            }
+        }
+    }
+}
+
+/// Borrowing breadth-first iterator over a `TreeNode<T>`, created by [`TreeNode::level_order`]
+pub struct LevelOrder<'a, T: Ord> {
+    // Subtrees still to be visited, in the order they were reached
+    queue: VecDeque<&'a TreeNode<T>>,
+}
+
+impl<'a, T: Ord> Iterator for LevelOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let TreeNode::Node(value, left, right) = self.queue.pop_front()? {
+                self.queue.push_back(left);
+                self.queue.push_back(right);
+                return Some(value);
+            }
+        }
+    }
+}
+
+/// Consuming in-order iterator over a `TreeNode<T>`, created by `into_iter`
+pub struct IntoIter<T: Ord> {
+    // Values still to be yielded, each with the right subtree to descend into after it. The top
+    // of the stack is the next value.
+    stack: Vec<(T, TreeNode<T>)>,
+}
+
+impl<T: Ord> IntoIter<T> {
+    /// Pushes `node` and its chain of left children onto the stack
+    fn push_left_spine(&mut self, mut node: TreeNode<T>) {
+        while let TreeNode::Node(value, left, right) = node {
+            self.stack.push((value, *right));
+            node = *left;
+        }
+    }
+}
+
+impl<T: Ord> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, right) = self.stack.pop()?;
+        self.push_left_spine(right);
+        Some(value)
+    }
+}
+
+impl<T: Ord> IntoIterator for TreeNode<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = IntoIter { stack: Vec::new() };
+        iter.push_left_spine(self);
+        iter
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a TreeNode<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,281 @@
+use std::cmp::Ordering;
+
+use crate::sorted_set::SortedSet;
+
+/// A treap: a binary search tree on the values that is also a max-heap on random priorities.
+///
+/// Each node draws its priority when it is inserted, so the shape of the tree is that of a binary
+/// search tree built by inserting the values in random order, and the expected height is
+/// O(log n) whatever the order of the insertions. Priorities come from a deterministic generator,
+/// so the same sequence of updates always gives the same tree.
+#[derive(Clone, Debug)]
+pub struct Treap<T: Ord> {
+    root: Link<T>,
+    // State of the xorshift generator that priorities are drawn from
+    seed: u64,
+}
+
+type Link<T> = Option<Box<TreapNode<T>>>;
+
+#[derive(Clone, Debug)]
+struct TreapNode<T: Ord> {
+    value: T,
+    priority: u64,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// Returns the priority of `link`, with empty subtrees below every node
+fn priority<T: Ord>(link: &Link<T>) -> Option<u64> {
+    link.as_ref().map(|node| node.priority)
+}
+
+/// Lifts the right child of `link` above it
+fn rotate_left<T: Ord>(link: &mut Link<T>) {
+    if let Some(mut node) = link.take() {
+        match node.right.take() {
+            Some(mut pivot) => {
+                node.right = pivot.left.take();
+                pivot.left = Some(node);
+                *link = Some(pivot);
+            }
+            None => *link = Some(node),
+        }
+    }
+}
+
+/// Lifts the left child of `link` above it
+fn rotate_right<T: Ord>(link: &mut Link<T>) {
+    if let Some(mut node) = link.take() {
+        match node.left.take() {
+            Some(mut pivot) => {
+                node.left = pivot.right.take();
+                pivot.right = Some(node);
+                *link = Some(pivot);
+            }
+            None => *link = Some(node),
+        }
+    }
+}
+
+/// Inserts `value` with priority `priority` below `link`, rotating it up past every node with a
+/// lower priority
+fn insert<T: Ord>(link: &mut Link<T>, value: T, priority: u64) {
+    let node = match link {
+        None => {
+            *link = Some(Box::new(TreapNode {
+                value,
+                priority,
+                left: None,
+                right: None,
+            }));
+            return;
+        }
+        Some(node) => node,
+    };
+    match value.cmp(&node.value) {
+        Ordering::Less => {
+            insert(&mut node.left, value, priority);
+            if priority_above(&node.left, node.priority) {
+                rotate_right(link);
+            }
+        }
+        Ordering::Greater => {
+            insert(&mut node.right, value, priority);
+            if priority_above(&node.right, node.priority) {
+                rotate_left(link);
+            }
+        }
+        Ordering::Equal => {}
+    }
+}
+
+/// Returns `true` if `link` is a node with a priority higher than `priority`
+fn priority_above<T: Ord>(link: &Link<T>, priority: u64) -> bool {
+    self::priority(link).is_some_and(|child| child > priority)
+}
+
+/// Removes `value` from below `link` and returns it, rotating its node down until it has at most
+/// one child and can be unlinked
+fn remove<T: Ord>(link: &mut Link<T>, value: &T) -> Option<T> {
+    let node = link.as_mut()?;
+    match value.cmp(&node.value) {
+        Ordering::Less => return remove(&mut node.left, value),
+        Ordering::Greater => return remove(&mut node.right, value),
+        Ordering::Equal => {}
+    }
+    match (priority(&node.left), priority(&node.right)) {
+        (None, _) => {
+            let node = link.take()?;
+            *link = node.right;
+            Some(node.value)
+        }
+        (_, None) => {
+            let node = link.take()?;
+            *link = node.left;
+            Some(node.value)
+        }
+        // Lift the child with the higher priority, keeping the heap order, and follow the node
+        (Some(left), Some(right)) if left > right => {
+            rotate_right(link);
+            remove(&mut link.as_mut()?.right, value)
+        }
+        _ => {
+            rotate_left(link);
+            remove(&mut link.as_mut()?.left, value)
+        }
+    }
+}
+
+impl<T: Ord> Treap<T> {
+    /// Creates a new empty treap
+    pub fn new() -> Self {
+        // Any fixed seed works, as long as the values are not drawn from the same sequence: a treap
+        // whose priorities follow its values degenerates into a list
+        Treap::with_seed(0x9e37_79b9_7f4a_7c15)
+    }
+
+    /// Creates a new empty treap whose priorities are drawn from a generator seeded with `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Treap {
+            root: None,
+            // Xorshift never leaves the all-zero state
+            seed: seed.max(1),
+        }
+    }
+
+    /// Returns `true` if the treap contains no values
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts `value` into the treap. If the value already exists in the treap, the function
+    /// does nothing.
+    pub fn insert(&mut self, value: T) {
+        let priority = self.next_priority();
+        insert(&mut self.root, value, priority);
+    }
+
+    /// Returns `true` if the treap contains `value`
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Removes `value` from the treap and returns it, or `None` if the value is not in the treap
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        remove(&mut self.root, value)
+    }
+
+    /// Returns the height of the treap
+    pub fn height(&self) -> usize {
+        fn height<T: Ord>(link: &Link<T>) -> usize {
+            link.as_ref()
+                .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+
+    /// Returns an iterator over references to the values of the treap, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        let mut link = &self.root;
+        std::iter::from_fn(move || {
+            while let Some(node) = link {
+                stack.push(node);
+                link = &node.left;
+            }
+            let node = stack.pop()?;
+            link = &node.right;
+            Some(&node.value)
+        })
+    }
+
+    /// Verifies that the treap is a binary search tree on its values and a max-heap on its
+    /// priorities
+    pub fn validate(&self) -> bool {
+        /// Returns `true` if the subtree at `link` is valid, all its values lie between `min` and
+        /// `max`, and no priority in it exceeds `max_priority`
+        fn check<T: Ord>(
+            link: &Link<T>,
+            min: Option<&T>,
+            max: Option<&T>,
+            max_priority: u64,
+        ) -> bool {
+            let node = match link {
+                None => return true,
+                Some(node) => node,
+            };
+            !(min.is_some_and(|min| node.value <= *min)
+                || max.is_some_and(|max| node.value >= *max)
+                || node.priority > max_priority)
+                && check(&node.left, min, Some(&node.value), node.priority)
+                && check(&node.right, Some(&node.value), max, node.priority)
+        }
+        check(&self.root, None, None, u64::MAX)
+    }
+
+    /// Draws the priority of the next inserted node
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+}
+
+impl<T: Ord> Default for Treap<T> {
+    fn default() -> Self {
+        Treap::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Treap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+        for value in iter {
+            treap.insert(value);
+        }
+        treap
+    }
+}
+
+impl<T: Ord> SortedSet<T> for Treap<T> {
+    fn new() -> Self {
+        Treap::new()
+    }
+
+    fn insert(&mut self, value: T) {
+        Treap::insert(self, value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        Treap::contains(self, value)
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        Treap::remove(self, value)
+    }
+
+    fn validate(&self) -> bool {
+        Treap::validate(self)
+    }
+
+    fn height(&self) -> usize {
+        Treap::height(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        Treap::iter(self)
+    }
+}
@@ -1,8 +1,19 @@
 #[allow(unused_imports)]
-use std::{cmp::Ord, mem};
+use std::{
+    borrow::Borrow,
+    cmp::{Ord, Ordering},
+    mem,
+};
 
-#[derive(Clone, Debug)]
+use crate::{
+    augment::Count,
+    avl_core::{self, Avl},
+    join::Join,
+};
+
+#[derive(Clone, Debug, Default)]
 pub enum TreeNode<T: Ord> {
+    #[default]
     Leaf,
     Node(T, Box<TreeNode<T>>, Box<TreeNode<T>>),
 }
@@ -22,21 +33,8 @@ impl<T: Ord> TreeNode<T> {
             match tree {
                 TreeNode::Leaf => true,
                 TreeNode::Node(value, left, right) => {
-                    match min {
-                        Some(min) => {
-                            if value <= min {
-                                return false;
-                            }
-                        }
-                        _ => {}
-                    }
-                    match max {
-                        Some(max) => {
-                            if value >= max {
-                                return false;
-                            }
-                        }
-                        _ => {}
+                    if min.is_some_and(|min| value <= min) || max.is_some_and(|max| value >= max) {
+                        return false;
                     }
                     is_bst_helper(left, min, Some(value)) && is_bst_helper(right, Some(value), max)
                 }
@@ -96,19 +94,68 @@ impl<T: Ord> TreeNode<T> {
         self.rebalance();
     }
 
-    /// Computes the balance factor of the tree (the difference between the height of the left and right subtrees)
-    fn balance_factor(&self) -> i32 {
-        match self {
-            TreeNode::Leaf => 0,
-            TreeNode::Node(_, left, right) => {
-                fn height<U: Ord>(node: &TreeNode<U>) -> i32 {
-                    match node {
-                        TreeNode::Leaf => 0,
-                        TreeNode::Node(_, l, r) => 1 + std::cmp::max(height(l), height(r)),
-                    }
+    /// Removes the node with value `value` from the tree and returns its value, or `None` if the
+    /// value is not in the tree.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let removed = match self {
+            TreeNode::Leaf => return None,
+            TreeNode::Node(val, left, right) => match value.cmp(val) {
+                Ordering::Less => left.remove(value),
+                Ordering::Greater => right.remove(value),
+                Ordering::Equal => Some(self.remove_root()),
+            },
+        };
+        self.rebalance();
+        removed
+    }
+
+    /// Removes the smallest value from the tree and returns it, or `None` if the tree is empty
+    pub fn pop_min(&mut self) -> Option<T> {
+        let min = match self {
+            TreeNode::Leaf => return None,
+            TreeNode::Node(_, left, _) if matches!(**left, TreeNode::Node(..)) => left.pop_min(),
+            TreeNode::Node(..) => match mem::take(self) {
+                TreeNode::Node(value, _, right) => {
+                    *self = *right;
+                    Some(value)
+                }
+                TreeNode::Leaf => unreachable!(),
+            },
+        };
+        self.rebalance();
+        min
+    }
+
+    /// Removes the largest value from the tree and returns it, or `None` if the tree is empty
+    pub fn pop_max(&mut self) -> Option<T> {
+        let max = match self {
+            TreeNode::Leaf => return None,
+            TreeNode::Node(_, _, right) if matches!(**right, TreeNode::Node(..)) => right.pop_max(),
+            TreeNode::Node(..) => match mem::take(self) {
+                TreeNode::Node(value, left, _) => {
+                    *self = *left;
+                    Some(value)
                 }
-                height(left) - height(right)
+                TreeNode::Leaf => unreachable!(),
+            },
+        };
+        self.rebalance();
+        max
+    }
+
+    /// Removes the value at the root of the tree, replacing it with its in-order successor
+    pub(crate) fn remove_root(&mut self) -> T {
+        match mem::take(self) {
+            TreeNode::Node(value, left, mut right) => {
+                *self = match right.pop_min() {
+                    Some(successor) => TreeNode::Node(successor, left, right),
+                    None => *left,
+                };
+                value
             }
+            TreeNode::Leaf => panic!("cannot remove the root of a leaf"),
         }
     }
 
@@ -148,35 +195,279 @@ impl<T: Ord> TreeNode<T> {
             }
         }
     }
+}
+
+// Lookup functions. Each follows a single path from the root, so they run in O(height), and like
+// `BTreeSet` they accept any borrowed form `Q` of the value type.
+impl<T: Ord> TreeNode<T> {
+    /// Returns `true` if the tree contains `value`
+    pub fn contains<Q: Ord + ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.get(value).is_some()
+    }
+
+    /// Returns a reference to the value in the tree equal to `value`, if any
+    pub fn get<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        let mut node = self;
+        while let TreeNode::Node(val, left, right) = node {
+            match value.cmp(val.borrow()) {
+                Ordering::Less => node = left,
+                Ordering::Greater => node = right,
+                Ordering::Equal => return Some(val),
+            }
+        }
+        None
+    }
+
+    /// Returns a reference to the smallest value in the tree, or `None` if the tree is empty
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self;
+        let mut min = None;
+        while let TreeNode::Node(value, left, _) = node {
+            min = Some(value);
+            node = left;
+        }
+        min
+    }
+
+    /// Returns a reference to the largest value in the tree, or `None` if the tree is empty
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self;
+        let mut max = None;
+        while let TreeNode::Node(value, _, right) = node {
+            max = Some(value);
+            node = right;
+        }
+        max
+    }
+
+    /// Returns the greatest value in the tree that is less than or equal to `value`
+    pub fn floor<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        self.below(value, true)
+    }
+
+    /// Returns the least value in the tree that is greater than or equal to `value`
+    pub fn ceiling<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        self.above(value, true)
+    }
+
+    /// Returns the greatest value in the tree that is strictly less than `value`
+    pub fn predecessor<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        self.below(value, false)
+    }
+
+    /// Returns the least value in the tree that is strictly greater than `value`
+    pub fn successor<Q: Ord + ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        self.above(value, false)
+    }
+
+    /// Returns the greatest value less than `value`, or equal to it if `inclusive`
+    fn below<Q: Ord + ?Sized>(&self, value: &Q, inclusive: bool) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut best = None;
+        while let TreeNode::Node(val, left, right) = node {
+            match val.borrow().cmp(value) {
+                Ordering::Equal if inclusive => return Some(val),
+                Ordering::Less => {
+                    best = Some(val);
+                    node = right;
+                }
+                _ => node = left,
+            }
+        }
+        best
+    }
+
+    /// Returns the least value greater than `value`, or equal to it if `inclusive`
+    fn above<Q: Ord + ?Sized>(&self, value: &Q, inclusive: bool) -> Option<&T>
+    where
+        T: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut best = None;
+        while let TreeNode::Node(val, left, right) = node {
+            match val.borrow().cmp(value) {
+                Ordering::Equal if inclusive => return Some(val),
+                Ordering::Greater => {
+                    best = Some(val);
+                    node = left;
+                }
+                _ => node = right,
+            }
+        }
+        best
+    }
+}
+
+// Bulk construction from sorted input. The tree is laid out directly from the sorted values, which
+// takes O(n) time and no rotations.
+impl<T: Ord> TreeNode<T> {
+    /// Builds a perfectly balanced tree from values in strictly ascending order.
+    ///
+    /// Panics if the values are not in strictly ascending order
+    pub fn from_sorted<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        assert!(
+            values.windows(2).all(|pair| pair[0] < pair[1]),
+            "values passed to `from_sorted` must be in strictly ascending order"
+        );
+        TreeNode::build_balanced(values)
+    }
+
+    /// Builds a perfectly balanced tree from values in ascending order, keeping only the first of
+    /// each run of equal values.
+    ///
+    /// Panics if the values are not in ascending order
+    pub fn from_sorted_dedup<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values: Vec<T> = iter.into_iter().collect();
+        assert!(
+            values.windows(2).all(|pair| pair[0] <= pair[1]),
+            "values passed to `from_sorted_dedup` must be in ascending order"
+        );
+        values.dedup();
+        TreeNode::build_balanced(values)
+    }
+
+    /// Builds a perfectly balanced tree from values that are already strictly ascending
+    fn build_balanced(values: Vec<T>) -> Self {
+        /// Builds a tree from the next `len` values of `values`, taking the middle one as the root
+        fn build<T: Ord>(values: &mut impl Iterator<Item = T>, len: usize) -> TreeNode<T> {
+            if len == 0 {
+                return TreeNode::Leaf;
+            }
+            let left = build(values, len / 2);
+            let value = values.next().expect("fewer values than counted");
+            let right = build(values, len - len / 2 - 1);
+            TreeNode::node(value, left, right)
+        }
+        let len = values.len();
+        build(&mut values.into_iter(), len)
+    }
+}
+
+/// A binary tree representation whose nodes can be inspected one at a time, so read-only
+/// algorithms such as printing can be shared between representations
+pub(crate) trait Shape {
+    type Value;
+
+    /// Returns the left subtree, root value and right subtree, or `None` if the tree is empty
+    fn parts(&self) -> Option<(&Self, &Self::Value, &Self)>;
+}
+
+/// A binary search tree representation that the AVL rebalancing algorithm can operate on.
+///
+/// Each representation provides its own rotations, which keep whatever it caches per node up to
+/// date, and shares the rebalancing logic itself.
+pub(crate) trait Rebalance {
+    /// Computes the balance factor of the tree (the difference between the height of the left and
+    /// right subtrees)
+    fn balance_factor(&self) -> i32;
+
+    /// Performs a left rotation on the tree
+    fn left_rotate(&mut self);
+
+    /// Performs a right rotation on the tree
+    fn right_rotate(&mut self);
+
+    /// Returns the left and right subtrees, or `None` if the tree is empty
+    fn children_mut(&mut self) -> Option<(&mut Self, &mut Self)>;
 
     /// Rebalances the tree using either a single or double rotation, as specified in the AVL tree
     /// rebalancing algorithm.
     fn rebalance(&mut self) {
         let balance = self.balance_factor();
-        match self {
-            TreeNode::Leaf => {}
-            TreeNode::Node(_, left, right) => {
-                if balance > 1 {
-                    if left.balance_factor() < 0 {
-                        left.left_rotate();
-                    }
-                    self.right_rotate();
-                } else if balance < -1 {
-                    if right.balance_factor() > 0 {
-                        right.right_rotate();
-                    }
-                    self.left_rotate();
+        if balance > 1 {
+            if let Some((left, _)) = self.children_mut() {
+                if left.balance_factor() < 0 {
+                    left.left_rotate();
+                }
+            }
+            self.right_rotate();
+        } else if balance < -1 {
+            if let Some((_, right)) = self.children_mut() {
+                if right.balance_factor() > 0 {
+                    right.right_rotate();
                 }
             }
+            self.left_rotate();
         }
     }
 }
 
-// Implement `Default` for `TreeNode<T>`
-impl<T: Ord> Default for TreeNode<T> {
-    fn default() -> Self {
+impl<T: Ord> Rebalance for TreeNode<T> {
+    fn balance_factor(&self) -> i32 {
+        match self {
+            TreeNode::Leaf => 0,
+            TreeNode::Node(_, left, right) => left.height() as i32 - right.height() as i32,
+        }
+    }
+
+    fn left_rotate(&mut self) {
+        TreeNode::left_rotate(self);
+    }
+
+    fn right_rotate(&mut self) {
+        TreeNode::right_rotate(self);
+    }
+
+    fn children_mut(&mut self) -> Option<(&mut Self, &mut Self)> {
+        match self {
+            TreeNode::Leaf => None,
+            TreeNode::Node(_, left, right) => Some((left, right)),
+        }
+    }
+}
+
+impl<T: Ord> Join for TreeNode<T> {
+    fn empty() -> Self {
         TreeNode::Leaf
     }
+
+    fn height(&self) -> usize {
+        TreeNode::height(self)
+    }
+
+    fn node(left: Self, value: T, right: Self) -> Self {
+        TreeNode::node(value, left, right)
+    }
+
+    fn expose(self) -> Option<(Self, T, Self)> {
+        match self {
+            TreeNode::Leaf => None,
+            TreeNode::Node(value, left, right) => Some((*left, value, *right)),
+        }
+    }
+}
+
+impl<T: Ord> Shape for TreeNode<T> {
+    type Value = T;
+
+    fn parts(&self) -> Option<(&Self, &T, &Self)> {
+        match self {
+            TreeNode::Leaf => None,
+            TreeNode::Node(value, left, right) => Some((left, value, right)),
+        }
+    }
 }
 
 // Implement `PartialEq` for `TreeNode<T>`
@@ -195,9 +486,21 @@ impl<T: Ord> Eq for TreeNode<T> {}
 
 // Implement `From<Vec<T>>` for `TreeNode<T>`
 impl<T: Ord> From<Vec<T>> for TreeNode<T> {
+    /// Sorted input is laid out directly in O(n); anything else is inserted value by value
     fn from(vec: Vec<T>) -> Self {
+        if vec.windows(2).all(|pair| pair[0] <= pair[1]) {
+            TreeNode::from_sorted_dedup(vec)
+        } else {
+            vec.into_iter().collect()
+        }
+    }
+}
+
+// Implement `FromIterator<T>` for `TreeNode<T>`
+impl<T: Ord> FromIterator<T> for TreeNode<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut tree = TreeNode::Leaf;
-        for value in vec {
+        for value in iter {
             tree.insert(value);
         }
         tree
@@ -222,3 +525,290 @@ impl<T: Ord> From<TreeNode<T>> for Vec<T> {
         vec
     }
 }
+
+/// An ordered map from keys to values, stored as key/value entries in the same AVL tree as
+/// `AvlTree<T>`, ordered by key.
+///
+/// Every node caches the height and size of its subtree, so insertion, lookup and removal run in
+/// O(log n) and `len` in O(1).
+#[derive(Clone)]
+pub struct AvlMap<K: Ord, V>(Avl<(K, V), Count>);
+
+/// Orders map entries by key
+fn cmp_keys<K: Ord, V>(a: &(K, V), b: &(K, V)) -> Ordering {
+    a.0.cmp(&b.0)
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// Creates a new empty map
+    pub fn new() -> Self {
+        AvlMap(Avl::new())
+    }
+
+    /// Returns the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the map contains no entries
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the height of the underlying tree
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Inserts `value` under `key` and returns the value previously stored under `key`, if any.
+    /// The key already in the map is kept.
+    ///
+    /// After insertion, the tree is rebalanced if necessary
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.0.insert_by((key, value), &cmp_keys) {
+            Ok(()) => None,
+            // The tree was left untouched, so the entry is still where it was found
+            Err((key, value)) => self.get_mut(&key).map(|old| mem::replace(old, value)),
+        }
+    }
+
+    /// Returns `true` if the map contains an entry for `key`
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value stored under `key`, or `None` if there is none
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0
+            .find(|(other, _)| key.cmp(other.borrow()))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns a mutable reference to the value stored under `key`, or `None` if there is none
+    pub fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0
+            .get_mut_by(&|(other, _)| key.cmp(other.borrow()))
+            .map(|(_, value)| value)
+    }
+
+    /// Removes the entry for `key` from the map and returns its value, or `None` if there is none.
+    ///
+    /// After removal, the tree is rebalanced if necessary
+    pub fn remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0
+            .remove_by(&|(other, _)| key.cmp(other.borrow()))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the entry for `key`, which can be used to update or insert its value in place
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let (rank, found) = self.0.rank_by(|(other, _)| key.cmp(other));
+        if found {
+            let (key, value) = self.0.nth_mut(rank).expect("a found key has a rank");
+            Entry::Occupied(OccupiedEntry { key, value })
+        } else {
+            Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                rank,
+            })
+        }
+    }
+
+    /// Returns an iterator over the entries of the map, in ascending order of keys
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter {
+            iter: self.0.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys of the map, in ascending order
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values of the map, in ascending order of their keys
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Verifies that the map is a valid balanced binary search tree whose cached heights and sizes
+    /// are correct
+    pub fn validate(&self) -> bool {
+        self.0.validate_by(cmp_keys)
+    }
+}
+
+impl<K: Ord, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        AvlMap::new()
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for AvlMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for AvlMap<K, V> {
+    /// Two maps are equal if they hold the same entries, whatever the shape of their trees
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for AvlMap<K, V> {}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a AvlMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Borrowing iterator over the entries of an `AvlMap<K, V>`, created by [`AvlMap::iter`]
+pub struct MapIter<'a, K: Ord, V> {
+    iter: avl_core::Iter<'a, (K, V), Count>,
+}
+
+impl<'a, K: Ord, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, value)| (key, value))
+    }
+}
+
+/// A view into a single entry of an `AvlMap<K, V>`, created by [`AvlMap::entry`]
+pub enum Entry<'a, K: Ord, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// An entry whose key is in the map
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    key: &'a K,
+    value: &'a mut V,
+}
+
+/// An entry whose key is not in the map
+pub struct VacantEntry<'a, K: Ord, V> {
+    map: &'a mut AvlMap<K, V>,
+    key: K,
+    // Number of keys in the map smaller than `key`, i.e. the position the entry will take
+    rank: usize,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// Returns the key of the entry
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a mutable reference to
+    /// the value
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Calls `f` on the value if the entry is occupied, and returns the entry
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+    /// Inserts the default value if the entry is vacant, and returns a mutable reference to the
+    /// value
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry
+    pub fn key(&self) -> &K {
+        self.key
+    }
+
+    /// Returns a reference to the value of the entry
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    /// Returns a mutable reference to the value of the entry
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /// Converts the entry into a mutable reference to its value that lives as long as the map
+    /// borrow
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+
+    /// Replaces the value of the entry and returns the old one
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.value, value)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// Returns the key of the entry
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes back ownership of the key
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts `value` under the key of the entry and returns a mutable reference to it
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.insert(self.key, value);
+        // Rebalancing may have moved the new node anywhere along its path, but its position in
+        // key order is known, so we find it again by rank
+        let entry = self.map.0.nth_mut(self.rank);
+        &mut entry.expect("the new entry is in the map").1
+    }
+}
@@ -0,0 +1,107 @@
+use links::augment::*;
+use quickcheck::quickcheck;
+use std::{collections::BTreeSet, ops::Bound};
+
+/// Counts the even values, as an example of a user-defined count-where augmentation
+struct CountEven;
+
+impl Augment<i32> for CountEven {
+    type Summary = usize;
+
+    fn identity() -> usize {
+        0
+    }
+
+    fn summarize(value: &i32) -> usize {
+        (value % 2 == 0) as usize
+    }
+
+    fn combine(left: &usize, right: &usize) -> usize {
+        left + right
+    }
+}
+
+/// Collects the values in order; `combine` is not commutative, so this checks that summaries are
+/// always combined from left to right
+struct Collect;
+
+impl Augment<i32> for Collect {
+    type Summary = Vec<i32>;
+
+    fn identity() -> Vec<i32> {
+        Vec::new()
+    }
+
+    fn summarize(value: &i32) -> Vec<i32> {
+        vec![*value]
+    }
+
+    fn combine(left: &Vec<i32>, right: &Vec<i32>) -> Vec<i32> {
+        left.iter().chain(right).copied().collect()
+    }
+}
+
+/// This test checks range aggregates with the provided and a user-defined augmentation
+#[test]
+fn test_fold_range_simple_5() {
+    let mut sums: AugmentedTree<i64, Sum> = (1..=100).collect();
+    assert_eq!(sums.summary(), 5050);
+    assert_eq!(sums.fold_range(1..=10), 55);
+    assert_eq!(sums.fold_range(91..), 955);
+    assert_eq!(sums.fold_range(..0), 0);
+    sums.remove(&5);
+    assert_eq!(sums.fold_range(1..=10), 50);
+    assert!(sums.validate());
+
+    let counts: AugmentedTree<&str, Count> =
+        vec!["pear", "apple", "fig", "plum"].into_iter().collect();
+    assert_eq!(counts.fold_range("b".."p"), 1);
+    assert_eq!(counts.fold_range("p"..), 2);
+
+    let evens: AugmentedTree<i32, CountEven> = (0..50).collect();
+    assert_eq!(evens.fold_range(10..20), 5);
+    assert_eq!(
+        evens.fold_range((Bound::Excluded(10), Bound::Included(20))),
+        5
+    );
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that after random inserts and removes the cached summaries are
+/// correct and `fold_range` over any range gives the same result as folding the values of
+/// `BTreeSet::range` in order.
+#[test]
+fn test_fold_range_10() {
+    fn matches_btreeset(ops: Vec<(bool, i32)>, ranges: Vec<(i32, i32, u8)>) -> bool {
+        let mut t: AugmentedTree<i32, Collect> = AugmentedTree::new();
+        let mut expected = BTreeSet::new();
+        for (insert, x) in ops {
+            if insert {
+                t.insert(x);
+                expected.insert(x);
+            } else {
+                t.remove(&x);
+                expected.remove(&x);
+            }
+        }
+        let folds_ok = ranges.into_iter().all(|(a, b, kind)| {
+            let (lo, hi) = (a.min(b), a.max(b));
+            let range = match kind % 4 {
+                0 => (Bound::Included(lo), Bound::Excluded(hi)),
+                1 => (Bound::Excluded(lo), Bound::Included(hi)),
+                2 => (Bound::Included(lo), Bound::Unbounded),
+                _ => (Bound::Unbounded, Bound::Included(hi)),
+            };
+            let excluded_both = matches!(range, (Bound::Excluded(_), _)) && lo == hi;
+            let brute: Vec<i32> = if excluded_both {
+                Vec::new()
+            } else {
+                expected.range(range).copied().collect()
+            };
+            t.fold_range(range) == brute
+        });
+        t.validate() && t.summary() == expected.iter().copied().collect::<Vec<_>>() && folds_ok
+    }
+    quickcheck(matches_btreeset as fn(Vec<(bool, i32)>, Vec<(i32, i32, u8)>) -> bool);
+}
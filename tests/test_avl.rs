@@ -0,0 +1,153 @@
+use links::avl::*;
+use quickcheck::quickcheck;
+use std::collections::BTreeSet;
+
+/// This test checks that the cached heights stay correct through single and double rotations
+#[test]
+fn test_rotations_keep_heights_5() {
+    for order in [[1, 2, 3], [3, 2, 1], [1, 3, 2], [3, 1, 2]] {
+        let mut t = AvlTree::new();
+        for x in order {
+            t.insert(x);
+            assert!(t.validate());
+        }
+        assert_eq!(t.height(), 2);
+    }
+}
+
+/// This test checks that inserting many sorted keys keeps the tree logarithmic in height
+#[test]
+fn test_sorted_insert_height_3() {
+    let t: AvlTree<u32> = (0..100_000).collect();
+    assert!(t.validate());
+    // An AVL tree with n nodes has height below 1.45 * log2(n + 2)
+    assert!(t.height() <= 24);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that the tree stays valid and holds the same values as a
+/// `BTreeSet` under any mix of inserts, removes, `pop_min` and `pop_max`.
+#[test]
+fn test_insert_remove_10() {
+    fn matches_btreeset(ops: Vec<(u8, i8)>) -> bool {
+        let mut t = AvlTree::new();
+        let mut expected = BTreeSet::new();
+        for (op, x) in ops {
+            let ok = match op % 4 {
+                0 | 1 => {
+                    t.insert(x);
+                    expected.insert(x);
+                    true
+                }
+                2 => t.remove(&x) == expected.take(&x),
+                _ => t.pop_min() == expected.pop_first() && t.pop_max() == expected.pop_last(),
+            };
+            if !ok || !t.validate() || t.is_empty() != expected.is_empty() {
+                return false;
+            }
+        }
+        let v: Vec<i8> = t.into();
+        v == expected.into_iter().collect::<Vec<_>>()
+    }
+    quickcheck(matches_btreeset as fn(Vec<(u8, i8)>) -> bool);
+}
+
+/// This test checks the order-statistic queries on a small tree
+#[test]
+fn test_order_statistics_simple_5() {
+    let t: AvlTree<i32> = vec![50, 10, 40, 20, 30].into();
+    assert_eq!(t.len(), 5);
+    assert_eq!(t.rank(&10), 0);
+    assert_eq!(t.rank(&35), 3);
+    assert_eq!(t.rank(&99), 5);
+    assert_eq!(t.select(0), Some(&10));
+    assert_eq!(t.select(4), Some(&50));
+    assert_eq!(t.select(5), None);
+    assert_eq!(t.median(), Some(&30));
+
+    let mut t = t;
+    t.remove(&50);
+    assert_eq!(t.len(), 4);
+    assert_eq!(t.median(), Some(&20));
+    assert_eq!(AvlTree::<i32>::new().median(), None);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that `len`, `rank`, `select` and `median` agree with the sorted
+/// values of the tree.
+#[test]
+fn test_order_statistics_10() {
+    fn matches_sorted(v: Vec<i16>, removed: Vec<i16>, probes: Vec<i16>) -> bool {
+        let mut t: AvlTree<i16> = v.into_iter().collect();
+        for x in removed.iter() {
+            t.remove(x);
+        }
+        let sorted: Vec<i16> = t.clone().into();
+        let median = sorted.len().checked_sub(1).map(|n| &sorted[n / 2]);
+        t.validate()
+            && t.len() == sorted.len()
+            && t.median() == median
+            && (0..=sorted.len()).all(|k| t.select(k) == sorted.get(k))
+            && probes
+                .iter()
+                .all(|x| t.rank(x) == sorted.iter().filter(|y| *y < x).count())
+    }
+    quickcheck(matches_sorted as fn(Vec<i16>, Vec<i16>, Vec<i16>) -> bool);
+}
+
+/// This test checks that set operations between a large and a small tree only touch the paths
+/// to the small tree's values; rebuilding the large tree each time would take far too long
+#[test]
+fn test_set_algebra_large_3() {
+    let mut large: AvlTree<u32> = (0..1_000_000).map(|x| x * 2).collect();
+    for i in 0..1_000 {
+        let small: AvlTree<u32> = vec![i * 7, i * 7 + 1, 3_000_000 + i].into();
+        large = large.union(small);
+        let small: AvlTree<u32> = vec![i * 7 + 1, 3_000_000 + i].into();
+        large = large.difference(small);
+    }
+    assert!(large.validate());
+    // For odd `i`, the union adds the odd `i * 7` and the difference removes the even `i * 7 + 1`
+    assert_eq!(large.len(), 1_000_000);
+    assert_eq!(large.rank(&(999 * 7)), 999 * 7 / 2 + 1);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that `split`, `join` and the set operations built on them agree
+/// with `BTreeSet` and keep the cached heights and sizes valid.
+#[test]
+fn test_set_algebra_10() {
+    fn matches_btreeset(a: Vec<i8>, b: Vec<i8>, pivot: i8) -> bool {
+        let (ta, tb): (AvlTree<i8>, AvlTree<i8>) = (a.into(), b.into());
+        let (sa, sb): (BTreeSet<i8>, BTreeSet<i8>) = (
+            Vec::from(ta.clone()).into_iter().collect(),
+            Vec::from(tb.clone()).into_iter().collect(),
+        );
+        let same = |t: AvlTree<i8>, s: BTreeSet<i8>| {
+            t.validate() && t.len() == s.len() && Vec::from(t).into_iter().eq(s)
+        };
+
+        let (left, found, right) = ta.clone().split(&pivot);
+        let split_ok = same(left.clone(), sa.range(..pivot).copied().collect())
+            && same(
+                right.clone(),
+                sa.range(pivot..).filter(|x| **x != pivot).copied().collect(),
+            )
+            && found == sa.get(&pivot).copied();
+        let mut with_pivot = sa.clone();
+        with_pivot.insert(pivot);
+
+        split_ok
+            && same(AvlTree::join(left, pivot, right), with_pivot)
+            && same(ta.clone().union(tb.clone()), &sa | &sb)
+            && same(ta.clone().intersection(tb.clone()), &sa & &sb)
+            && same(ta.clone().difference(tb.clone()), &sa - &sb)
+            && same(ta.clone().symmetric_difference(tb.clone()), &sa ^ &sb)
+            && ta.clone().is_subset(tb.clone()) == sa.is_subset(&sb)
+            && ta.is_disjoint(tb) == sa.is_disjoint(&sb)
+    }
+    quickcheck(matches_btreeset as fn(Vec<i8>, Vec<i8>, i8) -> bool);
+}
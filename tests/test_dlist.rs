@@ -0,0 +1,80 @@
+use links::dlist::*;
+use quickcheck::quickcheck;
+use std::collections::VecDeque;
+
+/// This test checks that `DList` mirrors the `ListNode` API
+#[test]
+pub fn test_insert_reverse_delete_5() {
+    let mut list = DList::new();
+    list.insert(1).insert(2).insert(3);
+    assert_eq!(format!("{}", list), "1 <-> 2 <-> 3 <-> Nil");
+    list.reverse();
+    assert_eq!(format!("{}", list), "3 <-> 2 <-> 1 <-> Nil");
+    list.delete();
+    assert_eq!(format!("{}", list), "2 <-> 1 <-> Nil");
+    list.delete();
+    list.delete();
+    list.delete();
+    assert_eq!(format!("{}", list), "Nil");
+    assert_eq!(list, DList::from(vec![]));
+}
+
+/// This test checks that a `DList` can be iterated from both ends
+#[test]
+pub fn test_double_ended_iter_3() {
+    let list: DList<i32> = vec![1, 2, 3, 4].into();
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    assert_eq!(list.iter().rev().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+    assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that a `DList<i32>` behaves like a `VecDeque<i32>` under any
+/// sequence of pushes, pops and reversals.
+#[test]
+pub fn test_ops_7() {
+    fn matches_vecdeque(ops: Vec<(u8, i32)>) -> bool {
+        let mut list = DList::new();
+        let mut expected = VecDeque::new();
+        for (op, x) in ops {
+            let ok = match op % 5 {
+                0 => {
+                    list.push_back(x);
+                    expected.push_back(x);
+                    true
+                }
+                1 => {
+                    list.push_front(x);
+                    expected.push_front(x);
+                    true
+                }
+                2 => list.pop_front() == expected.pop_front(),
+                3 => list.pop_back() == expected.pop_back(),
+                _ => {
+                    list.reverse();
+                    expected = expected.into_iter().rev().collect();
+                    true
+                }
+            };
+            if !ok
+                || list.len() != expected.len()
+                || list.front() != expected.front()
+                || list.back() != expected.back()
+                || !list.iter().rev().eq(expected.iter().rev())
+            {
+                return false;
+            }
+        }
+        list.into_iter().eq(expected)
+    }
+    quickcheck(matches_vecdeque as fn(Vec<(u8, i32)>) -> bool);
+}
@@ -0,0 +1,77 @@
+use links::interval::*;
+use quickcheck::quickcheck;
+use std::{collections::BTreeSet, ops::Range};
+
+/// This test checks point and range overlap queries on a small schedule
+#[test]
+fn test_overlapping_simple_5() {
+    let mut t: IntervalTree<u32> = vec![9..12, 1..5, 3..4, 6..10, 15..20].into_iter().collect();
+    assert!(t.validate());
+    assert_eq!(t.max_end(), Some(&20));
+
+    assert_eq!(
+        t.overlapping(3).cloned().collect::<Vec<_>>(),
+        vec![1..5, 3..4]
+    );
+    assert_eq!(
+        t.overlapping(9).cloned().collect::<Vec<_>>(),
+        vec![6..10, 9..12]
+    );
+    assert_eq!(t.overlapping(5).next(), None);
+    assert_eq!(t.overlapping(12).next(), None);
+    assert_eq!(
+        t.overlapping_range(4..7).cloned().collect::<Vec<_>>(),
+        vec![1..5, 6..10]
+    );
+    assert_eq!(t.overlapping_range(12..15).next(), None);
+    assert_eq!(t.overlapping_range(16..16).next(), None);
+
+    assert!(!t.insert(6..10));
+    assert!(t.remove(&(6..10)));
+    assert!(!t.remove(&(6..10)));
+    assert!(t.validate());
+    assert_eq!(t.overlapping(9).cloned().collect::<Vec<_>>(), vec![9..12]);
+    assert!(t.remove(&(15..20)));
+    assert_eq!(t.max_end(), Some(&12));
+    assert_eq!(t.len(), 3);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that after random inserts and removes the tree stays valid, and
+/// its overlap queries return the same intervals, in the same order, as filtering every stored
+/// interval by hand.
+#[test]
+fn test_overlapping_10() {
+    fn matches_brute_force(
+        ops: Vec<(bool, i8, i8)>,
+        points: Vec<i8>,
+        ranges: Vec<(i8, i8)>,
+    ) -> bool {
+        let mut t = IntervalTree::new();
+        let mut expected: BTreeSet<(i8, i8)> = BTreeSet::new();
+        for (insert, start, end) in ops {
+            let agrees = if insert {
+                t.insert(start..end) == expected.insert((start, end))
+            } else {
+                t.remove(&(start..end)) == expected.remove(&(start, end))
+            };
+            if !agrees || t.len() != expected.len() || !t.validate() {
+                return false;
+            }
+        }
+        let stored: Vec<Range<i8>> = expected.iter().map(|(start, end)| *start..*end).collect();
+        let points_ok = points.into_iter().all(|x| {
+            let brute = stored.iter().filter(|r| r.contains(&x));
+            t.overlapping(x).eq(brute)
+        });
+        let ranges_ok = ranges.into_iter().all(|(start, end)| {
+            let brute = stored
+                .iter()
+                .filter(|r| start < end && r.start < end && start < r.end && !r.is_empty());
+            t.overlapping_range(start..end).eq(brute)
+        });
+        t.iter().eq(stored.iter()) && points_ok && ranges_ok
+    }
+    quickcheck(matches_brute_force as fn(Vec<(bool, i8, i8)>, Vec<i8>, Vec<(i8, i8)>) -> bool);
+}
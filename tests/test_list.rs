@@ -1,3 +1,9 @@
+// The upstream tests are written in a style clippy flags; allow only the lints they trip
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::needless_return,
+    clippy::single_match,
+)]
 use links::list::*;
 #[allow(unused_imports)]
 use quickcheck::quickcheck;
@@ -235,14 +241,14 @@ pub fn test_insert_7() {
         }
 
         // check result
-        let mut curr = head;
+        let mut curr = &head;
         for v in v.iter() {
             match curr {
                 ListNode::Cons(x, next) => {
-                    if x != *v {
+                    if *x != *v {
                         return false;
                     }
-                    curr = *next;
+                    curr = next;
                 }
                 ListNode::Nil => {
                     return false;
@@ -272,14 +278,14 @@ pub fn test_reverse_7() {
         // reverse
         head.reverse();
         // check result
-        let mut curr = head;
+        let mut curr = &head;
         for v in v.iter().rev() {
             match curr {
                 ListNode::Cons(x, next) => {
-                    if x != *v {
+                    if *x != *v {
                         return false;
                     }
-                    curr = *next;
+                    curr = next;
                 }
                 ListNode::Nil => {
                     return false;
@@ -298,10 +304,10 @@ pub fn test_reverse_7() {
 pub fn test_impls_list_from_vec_3() {
     let v = vec![Num(1), Num(2), Num(3)];
     let list: ListNode<Num> = v.into();
-    match list {
-        ListNode::Cons(Num(1), next1) => match *next1 {
-            ListNode::Cons(Num(2), next2) => match *next2 {
-                ListNode::Cons(Num(3), next3) => match *next3 {
+    match &list {
+        ListNode::Cons(Num(1), next1) => match &**next1 {
+            ListNode::Cons(Num(2), next2) => match &**next2 {
+                ListNode::Cons(Num(3), next3) => match &**next3 {
                     ListNode::Nil => {
                         return;
                     }
@@ -375,4 +381,658 @@ pub fn test_vec_to_list_to_vec_roundtrip_8() {
 }
 
 
+
+/// This test checks that `iter` and `iter_mut` visit the values in order
+#[test]
+pub fn test_iter_3() {
+    let mut list: ListNode<i32> = vec![1, 2, 3].into();
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+    for x in list.iter_mut() {
+        *x *= 10;
+    }
+    for x in &mut list {
+        *x += 1;
+    }
+    assert_eq!((&list).into_iter().sum::<i32>(), 63);
+    assert_eq!(list.iter().next(), Some(&11));
+    assert_eq!(ListNode::<i32>::Nil.iter().next(), None);
+}
+
+/// This test checks that `into_iter` works for types that don't implement any traits
+#[test]
+pub fn test_into_iter_3() {
+    let list: ListNode<Num> = vec![Num(1), Num(2), Num(3)].into();
+    let values: Vec<i32> = list.into_iter().map(|Num(x)| x).collect();
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that collecting a `Vec<i32>` into a `ListNode<i32>`, extending it,
+/// and iterating over it should yield the same values as doing the same with a `Vec<i32>`.
+#[test]
+pub fn test_collect_extend_7() {
+    fn collect_extend(v: Vec<i32>, w: Vec<i32>) -> bool {
+        let mut list: ListNode<i32> = v.iter().copied().collect();
+        list.extend(w.iter().copied());
+
+        let mut expected = v.clone();
+        expected.extend(w);
+        list.iter().copied().eq(expected.iter().copied()) && list.into_iter().eq(expected)
+    }
+    quickcheck(collect_extend as fn(Vec<i32>, Vec<i32>) -> bool);
+}
+
+
+/// Number of nodes used by the long list tests; deep enough to overflow the stack if any of the
+/// operations below recursed once per node
+const LONG_LIST_LEN: i32 = 2_000_000;
+
+/// This test checks that comparing, cloning and inserting into a very long list does not overflow
+/// the stack
+#[test]
+pub fn test_long_list_eq_clone_insert_5() {
+    let mut list: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    let mut copy = list.clone();
+    assert!(list == copy);
+
+    copy.insert(LONG_LIST_LEN);
+    assert!(list != copy);
+    list.insert(LONG_LIST_LEN);
+    assert!(list == copy);
+}
+
+/// This test checks that displaying a very long list does not overflow the stack
+#[test]
+pub fn test_long_list_display_5() {
+    let list: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    let shown = format!("{}", list);
+    assert!(shown.starts_with("0 -> 1 -> 2 -> "));
+    assert!(shown.ends_with(&format!("{} -> Nil", LONG_LIST_LEN - 1)));
+}
+
+/// This test checks that dropping a partially consumed iterator over a very long list does not
+/// overflow the stack
+#[test]
+pub fn test_long_list_into_iter_drop_3() {
+    let list: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    let mut iter = list.into_iter();
+    assert_eq!(iter.next(), Some(0));
+    drop(iter);
+}
+
+
+/// This test checks that `LinkedList` keeps its length and both ends in sync
+#[test]
+pub fn test_linked_list_simple_3() {
+    let mut list = LinkedList::new();
+    assert!(list.is_empty());
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&1));
+    assert_eq!(format!("{}", list), "1 -> 2 -> 3 -> Nil");
+
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_front(), Some(2));
+    assert_eq!(list.pop_front(), Some(3));
+    assert_eq!(list.pop_front(), None);
+    list.push_back(4);
+    assert_eq!(Vec::from(list), vec![4]);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that a `LinkedList<i32>` behaves like a `VecDeque<i32>` under
+/// any sequence of `push_back`, `push_front` and `pop_front` operations.
+#[test]
+pub fn test_linked_list_ops_7() {
+    fn matches_vecdeque(ops: Vec<(u8, i32)>) -> bool {
+        let mut list = LinkedList::new();
+        let mut expected = std::collections::VecDeque::new();
+        for (op, x) in ops {
+            match op % 3 {
+                0 => {
+                    list.push_back(x);
+                    expected.push_back(x);
+                }
+                1 => {
+                    list.push_front(x);
+                    expected.push_front(x);
+                }
+                _ => {
+                    if list.pop_front() != expected.pop_front() {
+                        return false;
+                    }
+                }
+            }
+            if list.len() != expected.len() {
+                return false;
+            }
+        }
+        list.as_list().iter().eq(expected.iter())
+    }
+    quickcheck(matches_vecdeque as fn(Vec<(u8, i32)>) -> bool);
+}
+
+/// This test checks that converting a long `Vec` into a list takes linear time, and that a long
+/// `LinkedList` can be dropped without overflowing the stack
+#[test]
+pub fn test_long_list_from_vec_5() {
+    let v: Vec<i32> = (0..LONG_LIST_LEN).collect();
+    let list: ListNode<i32> = v.clone().into();
+    assert!(list.iter().eq(v.iter()));
+
+    let mut list: LinkedList<i32> = v.into();
+    list.push_back(LONG_LIST_LEN);
+    assert_eq!(list.len(), LONG_LIST_LEN as usize + 1);
+}
+
+
+/// This test checks the basic `CursorMut` operations on a small list
+#[test]
+pub fn test_cursor_simple_5() {
+    let mut list: ListNode<i32> = vec![1, 2, 4].into();
+    let mut cursor = list.cursor_mut();
+    assert_eq!(cursor.peek(), Some(&1));
+    assert!(cursor.move_next());
+    assert!(cursor.move_next());
+    cursor.insert_before(3);
+    assert_eq!(cursor.peek(), Some(&4));
+    assert_eq!(cursor.index(), 3);
+    cursor.insert_after(5);
+    *cursor.current().unwrap() *= 10;
+    assert_eq!(cursor.remove_current(), Some(40));
+    assert_eq!(cursor.peek(), Some(&5));
+    assert!(cursor.move_next());
+    assert!(cursor.is_end());
+    assert!(!cursor.move_next());
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(6);
+    assert_eq!(format!("{}", list), "1 -> 2 -> 3 -> 5 -> 6 -> Nil");
+
+    let mut cursor = list.cursor_mut();
+    cursor.move_next();
+    cursor.splice_before(vec![7, 8].into());
+    assert_eq!(cursor.peek(), Some(&2));
+    cursor.move_next();
+    let rest = cursor.split();
+    assert!(cursor.is_end());
+    assert_eq!(format!("{}", list), "1 -> 7 -> 8 -> 2 -> Nil");
+    assert_eq!(format!("{}", rest), "3 -> 5 -> 6 -> Nil");
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that editing a `ListNode<i32>` through a `CursorMut` has the
+/// same effect as making the same edits to a `Vec<i32>` at the cursor's index.
+#[test]
+pub fn test_cursor_ops_7() {
+    fn matches_vec(v: Vec<i32>, ops: Vec<(u8, i32)>) -> bool {
+        let mut list: ListNode<i32> = v.clone().into();
+        let mut expected = v;
+        let mut cursor = list.cursor_mut();
+        let mut split_off = None;
+        for (op, x) in ops {
+            let i = cursor.index();
+            match op % 5 {
+                0 => {
+                    if cursor.move_next() != (i < expected.len()) {
+                        return false;
+                    }
+                }
+                1 => {
+                    cursor.insert_before(x);
+                    expected.insert(i, x);
+                }
+                2 => {
+                    cursor.insert_after(x);
+                    expected.insert((i + 1).min(expected.len()), x);
+                }
+                3 => {
+                    let removed = (i < expected.len()).then(|| expected.remove(i));
+                    if cursor.remove_current() != removed {
+                        return false;
+                    }
+                }
+                _ => {
+                    if split_off.is_none() {
+                        split_off = Some((cursor.split(), expected.split_off(i)));
+                    }
+                }
+            }
+            if cursor.peek() != expected.get(cursor.index()) {
+                return false;
+            }
+        }
+        if let Some((tail, expected_tail)) = split_off {
+            if Vec::from(tail) != expected_tail {
+                return false;
+            }
+        }
+        Vec::from(list) == expected
+    }
+    quickcheck(matches_vec as fn(Vec<i32>, Vec<(u8, i32)>) -> bool);
+}
+
+
+/// This test checks that the positional operations report out-of-range indices
+#[test]
+pub fn test_positional_out_of_range_3() {
+    let mut list: ListNode<i32> = vec![1, 2, 3].into();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.get(3), None);
+    assert_eq!(list.get_mut(3), None);
+    assert_eq!(list.insert_at(4, 10), Err(10));
+    assert_eq!(list.remove_at(3), None);
+    assert!(list.split_off(4).is_none());
+    list.truncate(5);
+    assert_eq!(Vec::from(list), vec![1, 2, 3]);
+
+    let mut empty: ListNode<i32> = ListNode::new();
+    assert!(empty.is_empty());
+    empty.delete();
+    assert!(empty.is_empty());
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that the positional operations on a `ListNode<i32>` agree with
+/// the corresponding operations on a `Vec<i32>`.
+#[test]
+pub fn test_positional_ops_7() {
+    fn matches_vec(v: Vec<i32>, ops: Vec<(u8, usize, i32)>) -> bool {
+        let mut list: ListNode<i32> = v.clone().into();
+        let mut expected = v;
+        for (op, i, x) in ops {
+            let i = i % (expected.len() + 2);
+            let in_range = i < expected.len();
+            let ok = match op % 8 {
+                0 => list.get(i) == expected.get(i),
+                1 => {
+                    if let Some(y) = list.get_mut(i) {
+                        *y = x;
+                    }
+                    if let Some(y) = expected.get_mut(i) {
+                        *y = x;
+                    }
+                    true
+                }
+                2 => {
+                    let result = list.insert_at(i, x);
+                    if i <= expected.len() {
+                        expected.insert(i, x);
+                        result == Ok(())
+                    } else {
+                        result == Err(x)
+                    }
+                }
+                3 => list.remove_at(i) == in_range.then(|| expected.remove(i)),
+                4 => match list.split_off(i) {
+                    Some(rest) => i <= expected.len() && Vec::from(rest) == expected.split_off(i),
+                    None => i > expected.len(),
+                },
+                5 => {
+                    let mut other: ListNode<i32> = vec![x, x.wrapping_neg()].into();
+                    list.append(&mut other);
+                    expected.extend([x, x.wrapping_neg()]);
+                    other.is_empty()
+                }
+                6 => {
+                    list.truncate(i);
+                    expected.truncate(i);
+                    true
+                }
+                _ => {
+                    list.delete();
+                    if !expected.is_empty() {
+                        expected.remove(0);
+                    }
+                    true
+                }
+            };
+            if !ok || list.len() != expected.len() || list.is_empty() != expected.is_empty() {
+                return false;
+            }
+        }
+        Vec::from(list) == expected
+    }
+    quickcheck(matches_vec as fn(Vec<i32>, Vec<(u8, usize, i32)>) -> bool);
+}
+
+
+/// This test checks that `sort_by_key` and `dedup_by_key` work for types that don't implement any
+/// traits
+#[test]
+pub fn test_sort_dedup_no_traits_3() {
+    let mut list: ListNode<Num> = vec![Num(3), Num(1), Num(3), Num(2), Num(1)].into();
+    list.sort_by_key(|n| n.0);
+    list.dedup_by_key(|n| n.0);
+    let values: Vec<i32> = list.into_iter().map(|Num(x)| x).collect();
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that sorting a `ListNode` by key is stable and agrees with
+/// `sort_by_key` on a `Vec`.
+#[test]
+pub fn test_sort_stable_7() {
+    fn sort_matches_vec(v: Vec<(u8, i32)>) -> bool {
+        let mut list: ListNode<(u8, i32)> = v.clone().into();
+        let mut expected = v;
+        list.sort_by_key(|pair| pair.0 % 4);
+        expected.sort_by_key(|pair| pair.0 % 4);
+        Vec::from(list) == expected
+    }
+    quickcheck(sort_matches_vec as fn(Vec<(u8, i32)>) -> bool);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that `sort`, `dedup` and `merge_sorted` agree with the same
+/// operations on a `Vec`.
+#[test]
+pub fn test_sort_dedup_merge_7() {
+    fn matches_vec(v: Vec<i8>, w: Vec<i8>) -> bool {
+        let mut list: ListNode<i8> = v.clone().into();
+        let mut other: ListNode<i8> = w.clone().into();
+        list.sort();
+        other.sort();
+        let mut merged = ListNode::merge_sorted(list, other);
+        let mut expected: Vec<i8> = v.into_iter().chain(w).collect();
+        expected.sort();
+        if Vec::from(ListNode::clone(&merged)) != expected {
+            return false;
+        }
+        merged.dedup();
+        expected.dedup();
+        Vec::from(merged) == expected
+    }
+    quickcheck(matches_vec as fn(Vec<i8>, Vec<i8>) -> bool);
+}
+
+/// This test checks that sorting and deduplicating a very long list does not overflow the stack
+#[test]
+pub fn test_long_list_sort_5() {
+    let mut list: ListNode<i32> = (0..LONG_LIST_LEN).rev().map(|x| x / 2).collect();
+    list.sort();
+    list.dedup();
+    assert!(list.iter().copied().eq(0..LONG_LIST_LEN / 2));
+}
+
+/// This test checks that a comparator panicking partway through `sort_by` leaves every value in
+/// the list, whichever comparison it panics on
+#[test]
+pub fn test_sort_panic_keeps_values_5() {
+    let values = vec![5, 3, 8, 1, 9, 2, 7, 4, 6, 0];
+    for panic_at in 0.. {
+        let mut list: ListNode<i32> = values.clone().into();
+        let mut comparisons = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                comparisons += 1;
+                assert!(comparisons <= panic_at, "comparator gave up");
+                a.cmp(b)
+            })
+        }));
+        let mut left: Vec<i32> = list.into();
+        left.sort();
+        assert_eq!(left, (0..10).collect::<Vec<_>>());
+        if result.is_ok() {
+            break;
+        }
+    }
+}
+
+
+/// This test checks that the owned combinators work for types that don't implement any traits
+#[test]
+pub fn test_combinators_no_traits_5() {
+    let list: ListNode<Num> = vec![Num(1), Num(2), Num(3), Num(4)].into();
+    assert!(list.any(|n| n.0 == 3));
+    assert!(list.all(|n| n.0 > 0));
+    assert_eq!(list.find(|n| n.0 % 2 == 0).map(|n| n.0), Some(2));
+    assert_eq!(list.position(|n| n.0 == 4), Some(3));
+    assert_eq!(list.position(|n| n.0 == 5), None);
+    assert_eq!(Vec::from(list.filter_ref(|n| n.0 > 2).map(|n| n.0)), vec![3, 4]);
+
+    let (even, odd) = list.partition(|n| n.0 % 2 == 0);
+    let pairs = even.zip(odd);
+    let (mut evens, odds) = pairs.unzip();
+    evens.retain(|n| n.0 != 2);
+    let total = evens.fold(0, |acc, Num(x)| acc + x) + odds.fold_ref(0, |acc, n| acc + n.0);
+    assert_eq!(total, 8);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that the `ListNode` combinators agree with the corresponding
+/// iterator adapters on a `Vec`.
+#[test]
+pub fn test_combinators_7() {
+    fn matches_vec(v: Vec<i32>, w: Vec<u8>) -> bool {
+        let list: ListNode<i32> = v.clone().into();
+        let other: ListNode<u8> = w.clone().into();
+        let even = |x: &i32| x % 2 == 0;
+
+        let (yes, no): (Vec<i32>, Vec<i32>) = v.iter().partition(|x| even(x));
+        let (yes_ref, no_ref) = list.partition_ref(even);
+        let mut retained = ListNode::clone(&list);
+        retained.retain(even);
+        let zipped: Vec<(i32, u8)> = v.iter().copied().zip(w.iter().copied()).collect();
+        let (left, right) = ListNode::clone(&list).zip(ListNode::clone(&other)).unzip();
+
+        Vec::from(list.map_ref(|x| x / 2)) == v.iter().map(|x| x / 2).collect::<Vec<_>>()
+            && Vec::from(list.filter_map_ref(|x| x.checked_mul(3)))
+                == v.iter().filter_map(|x| x.checked_mul(3)).collect::<Vec<_>>()
+            && Vec::from(list.zip_ref(&other).map(|(a, b)| (*a, *b))) == zipped
+            && Vec::from(yes_ref.map(|x| *x)) == yes
+            && Vec::from(no_ref.map(|x| *x)) == no
+            && Vec::from(retained) == yes
+            && Vec::from(left) == zipped.iter().map(|p| p.0).collect::<Vec<_>>()
+            && Vec::from(right) == zipped.iter().map(|p| p.1).collect::<Vec<_>>()
+            && list.any(even) == v.iter().any(even)
+            && list.all(even) == v.iter().all(even)
+            && list.find(|x| even(x)) == v.iter().find(|x| even(x))
+            && list.position(even) == v.iter().position(even)
+            && list.fold_ref(0i64, |acc, x| acc + *x as i64) == v.iter().map(|x| *x as i64).sum()
+            && Vec::from(list.filter(even)) == yes
+    }
+    quickcheck(matches_vec as fn(Vec<i32>, Vec<u8>) -> bool);
+}
+
+
+/// This test checks that `ListNode` works as a stack and that `remove` returns the deleted value
+#[test]
+pub fn test_stack_3() {
+    let mut stack = ListNode::new();
+    assert_eq!(stack.peek(), None);
+    stack.push_front(1);
+    stack.push_front(2);
+    *stack.peek_mut().unwrap() += 10;
+    assert_eq!(stack.peek(), Some(&12));
+    assert_eq!(stack.pop_front(), Some(12));
+    stack.push_front(3);
+    assert_eq!(format!("{}", stack), "3 -> 1 -> Nil");
+
+    let second = match &mut stack {
+        ListNode::Cons(_, next) => next,
+        ListNode::Nil => unreachable!(),
+    };
+    assert_eq!(second.remove(), Some(1));
+    assert_eq!(second.remove(), None);
+    assert_eq!(stack.remove(), Some(3));
+    assert_eq!(stack.pop_front(), None);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that pushing and popping values at the front of a `ListNode<i32>`
+/// behaves like pushing and popping at the end of a `Vec<i32>` used as a stack.
+#[test]
+pub fn test_stack_ops_7() {
+    fn matches_vec(ops: Vec<Option<i32>>) -> bool {
+        let mut stack = ListNode::new();
+        let mut expected = Vec::new();
+        for op in ops {
+            match op {
+                Some(x) => {
+                    stack.push_front(x);
+                    expected.push(x);
+                }
+                None => {
+                    if stack.pop_front() != expected.pop() {
+                        return false;
+                    }
+                }
+            }
+            if stack.peek() != expected.last() {
+                return false;
+            }
+        }
+        expected.reverse();
+        Vec::from(stack) == expected
+    }
+    quickcheck(matches_vec as fn(Vec<Option<i32>>) -> bool);
+}
+
+
+/// This test checks that parsing a `ListNode` reports where and why the input is malformed
+#[test]
+pub fn test_parse_errors_5() {
+    assert_eq!("Nil".parse::<ListNode<i32>>(), Ok(ListNode::Nil));
+    assert_eq!(
+        "1 -> 2 -> Nil".parse::<ListNode<i32>>(),
+        Ok(vec![1, 2].into())
+    );
+
+    let err = "1 -> 2".parse::<ListNode<i32>>().unwrap_err();
+    assert_eq!(err.position(), 5);
+    assert_eq!(err.kind(), &ParseListErrorKind::MissingNil);
+
+    let err = "1 -> x -> Nil".parse::<ListNode<i32>>().unwrap_err();
+    assert_eq!(err.position(), 5);
+    assert!(matches!(err.kind(), ParseListErrorKind::InvalidElement(_)));
+    assert!(err.to_string().starts_with("invalid element at byte 5"));
+
+    let err = "1 -> Nil -> 2".parse::<ListNode<i32>>().unwrap_err();
+    assert_eq!(err.position(), 5);
+    assert!(matches!(err.kind(), ParseListErrorKind::InvalidElement(_)));
+
+    let err = "1 -> Nil\n".parse::<ListNode<i32>>().unwrap_err();
+    assert_eq!(err.position(), 8);
+    assert_eq!(err.kind(), &ParseListErrorKind::TrailingInput);
+
+    let err = "".parse::<ListNode<i32>>().unwrap_err();
+    assert_eq!(err.position(), 0);
+    assert_eq!(err.kind(), &ParseListErrorKind::MissingNil);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that parsing the `Display` output of a `ListNode` yields the same
+/// list.
+#[test]
+pub fn test_parse_display_roundtrip_7() {
+    fn roundtrip(v: Vec<i32>, w: Vec<f64>) -> bool {
+        let list: ListNode<i32> = v.into();
+        let floats: ListNode<f64> = w.into();
+        list.to_string().parse() == Ok(list)
+            && floats.to_string().parse::<ListNode<f64>>().map(|parsed| {
+                parsed.len() == floats.len()
+                    && parsed
+                        .iter()
+                        .zip(floats.iter())
+                        .all(|(a, b)| a == b || (a.is_nan() && b.is_nan()))
+            }) == Ok(true)
+    }
+    quickcheck(roundtrip as fn(Vec<i32>, Vec<f64>) -> bool);
+}
+
+
+/// This test checks that lists can be stored in hash sets, ordered sets and trees
+#[test]
+pub fn test_lists_as_keys_3() {
+    use links::tree::TreeNode;
+    use std::collections::{BTreeSet, HashSet};
+
+    let lists: Vec<ListNode<i32>> = vec![vec![2], vec![1, 2], vec![], vec![1], vec![1, 2]]
+        .into_iter()
+        .map(ListNode::from)
+        .collect();
+
+    let hashed: HashSet<&ListNode<i32>> = lists.iter().collect();
+    assert_eq!(hashed.len(), 4);
+
+    let ordered: BTreeSet<&ListNode<i32>> = lists.iter().collect();
+    let ordered: Vec<String> = ordered.iter().map(|l| l.to_string()).collect();
+    assert_eq!(ordered, vec!["Nil", "1 -> Nil", "1 -> 2 -> Nil", "2 -> Nil"]);
+
+    let mut tree = TreeNode::new();
+    for list in lists {
+        tree.insert(list);
+    }
+    assert!(tree.validate());
+    assert_eq!(Vec::from(tree).len(), 4);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that lists compare like the equivalent `Vec`s, and that equal
+/// lists hash equally.
+#[test]
+pub fn test_ord_hash_7() {
+    fn hash_of(list: &ListNode<i8>) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    }
+    fn matches_vec(v: Vec<i8>, w: Vec<i8>) -> bool {
+        let a: ListNode<i8> = v.clone().into();
+        let b: ListNode<i8> = w.clone().into();
+        a.cmp(&b) == v.cmp(&w)
+            && a.partial_cmp(&b) == v.partial_cmp(&w)
+            && hash_of(&a) == hash_of(&a.clone())
+            && (a != b || hash_of(&a) == hash_of(&b))
+    }
+    quickcheck(matches_vec as fn(Vec<i8>, Vec<i8>) -> bool);
+}
+
+/// This test checks that comparing and hashing very long lists does not overflow the stack
+#[test]
+pub fn test_long_list_ord_hash_3() {
+    use std::hash::{Hash, Hasher};
+
+    let a: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    let mut b = a.clone();
+    b.insert(0);
+    assert!(a < b);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    a.hash(&mut hasher);
+    let _ = hasher.finish();
+}
+
+/// This test checks that dropping a very long list does not overflow the stack, whether it goes
+/// out of scope, is overwritten, is split off or is thrown away by a failed parse
+#[test]
+pub fn test_long_list_drop_5() {
+    let list: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    drop(list);
+
+    let mut list: ListNode<i32> = (0..LONG_LIST_LEN).collect();
+    assert_eq!(list.len(), LONG_LIST_LEN as usize);
+    list = (0..LONG_LIST_LEN).collect();
+    let rest = list.split_off(1);
+    assert_eq!(rest.map(|rest| rest.len()), Some(LONG_LIST_LEN as usize - 1));
+    assert_eq!(list.len(), 1);
+
+    let mut text: String = (0..LONG_LIST_LEN).map(|x| format!("{} -> ", x)).collect();
+    text.push_str("x -> Nil");
+    assert!(text.parse::<ListNode<i32>>().is_err());
+}
+
 const _UNUSED: bool = true;
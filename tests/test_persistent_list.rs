@@ -0,0 +1,67 @@
+use links::{list::ListNode, persistent_list::*};
+use quickcheck::quickcheck;
+
+/// A wrapper over i32 that doesn't implement any traits
+struct Num(i32);
+
+/// This test checks that `cons`, `head` and `tail` share the suffix between versions
+#[test]
+pub fn test_cons_head_tail_5() {
+    let empty: PersistentList<Num> = PersistentList::new();
+    let one = empty.cons(Num(1));
+    let two = one.cons(Num(2));
+    let other = one.cons(Num(3));
+
+    assert!(empty.is_empty());
+    assert_eq!(two.len(), 2);
+    assert_eq!(two.head().map(|n| n.0), Some(2));
+    assert_eq!(other.head().map(|n| n.0), Some(3));
+    assert!(two.tail().unwrap().ptr_eq(&one));
+    assert!(other.tail().unwrap().ptr_eq(&one));
+    assert!(one.tail().unwrap().ptr_eq(&empty));
+    assert!(empty.tail().is_none());
+    assert_eq!(one.iter().map(|n| n.0).collect::<Vec<_>>(), vec![1]);
+}
+
+/// This test checks that `Arc`-backed versions can be shared between threads
+#[test]
+pub fn test_arc_list_threads_3() {
+    let list: ArcList<i32> = vec![1, 2, 3].into_iter().collect();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let list = list.clone();
+            std::thread::spawn(move || list.cons(i).iter().sum::<i32>())
+        })
+        .collect();
+    let sums: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(sums, vec![6, 7, 8, 9]);
+    assert_eq!(format!("{}", list), "1 -> 2 -> 3 -> Nil");
+}
+
+/// This test checks that dropping a long list, and a version sharing most of it, does not overflow
+/// the stack
+#[test]
+pub fn test_long_list_drop_3() {
+    let list: PersistentList<i32> = (0..2_000_000).collect();
+    let longer = list.cons(-1);
+    drop(list);
+    assert_eq!(longer.len(), 2_000_001);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that converting a `ListNode<i32>` to a `PersistentList<i32>` and
+/// back yields the same values, whether or not the nodes are shared with another version.
+#[test]
+pub fn test_list_roundtrip_7() {
+    fn roundtrip(v: Vec<i32>, x: i32) -> bool {
+        let list: PersistentList<i32> = ListNode::from(v.clone()).into();
+        let snapshot = list.cons(x);
+        let borrowed = ListNode::from(&list);
+        let shared = ListNode::from(snapshot.tail().unwrap());
+        drop(snapshot);
+        let owned = ListNode::from(list);
+        Vec::from(borrowed) == v && Vec::from(shared) == v && Vec::from(owned) == v
+    }
+    quickcheck(roundtrip as fn(Vec<i32>, i32) -> bool);
+}
@@ -0,0 +1,106 @@
+use links::{persistent_tree::*, tree::TreeNode};
+use quickcheck::quickcheck;
+use std::collections::BTreeSet;
+
+/// This test checks that updates leave earlier versions untouched
+#[test]
+pub fn test_versions_5() {
+    let empty: PersistentTree<i32> = PersistentTree::new();
+    let one = empty.insert(1);
+    let two = one.insert(2);
+    let three = two.insert(3);
+    let without_two = three.remove(&2);
+
+    assert!(empty.is_empty());
+    assert_eq!(one.iter().copied().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(two.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(three.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(without_two.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+    assert!(three.validate() && without_two.validate());
+
+    // Updates that change nothing share the whole tree
+    assert!(three.insert(2).ptr_eq(&three));
+    assert!(three.remove(&4).ptr_eq(&three));
+    assert!(!three.insert(4).ptr_eq(&three));
+}
+
+/// This test checks that `show` draws a version the same way it draws the `TreeNode` built by
+/// the same insertions
+#[test]
+pub fn test_show_3() {
+    let values = [5, 3, 8, 1, 4, 9, 12, 2];
+    let mut t = TreeNode::new();
+    let mut p: PersistentTree<i32> = PersistentTree::new();
+    for x in values {
+        t.insert(x);
+        p = p.insert(x);
+        assert_eq!(p.show(3), t.show(3));
+    }
+    t.remove(&5);
+    assert_eq!(p.remove(&5).show(3), t.show(3));
+}
+
+/// This test checks that a new version copies only a path through the tree: keeping thousands of
+/// versions of a large tree alive would take far too much memory if each were a full copy
+#[test]
+pub fn test_sharing_3() {
+    let base: PersistentTree<u32> = (0..100_000).map(|x| x * 2).collect();
+    let versions: Vec<PersistentTree<u32>> =
+        (0..10_000).map(|x| base.insert(x * 2 + 1).remove(&(x * 2))).collect();
+    assert_eq!(base.len(), 100_000);
+    for (x, version) in versions.iter().enumerate().step_by(997) {
+        let x = x as u32;
+        assert!(version.validate());
+        assert_eq!(version.len(), 100_000);
+        assert!(version.contains(&(x * 2 + 1)) && !version.contains(&(x * 2)));
+        assert!(!base.contains(&(x * 2 + 1)) && base.contains(&(x * 2)));
+    }
+}
+
+/// This test checks that `ArcTree` versions can be read from other threads
+#[test]
+pub fn test_arc_tree_3() {
+    let base: ArcTree<i32> = (0..100).collect();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let version = base.remove(&i);
+            std::thread::spawn(move || version.validate() && version.len() == 99)
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap());
+    }
+    assert_eq!(base.len(), 100);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that every version produced by a random sequence of inserts and
+/// removes stays valid and keeps holding the same values as a `BTreeSet` snapshot taken at the
+/// same step.
+#[test]
+pub fn test_versions_10() {
+    fn matches_snapshots(ops: Vec<(bool, i8)>) -> bool {
+        let mut versions: Vec<PersistentTree<i8>> = vec![PersistentTree::new()];
+        let mut snapshots = vec![BTreeSet::new()];
+        for (insert, x) in ops {
+            let mut snapshot = snapshots[snapshots.len() - 1].clone();
+            let last = &versions[versions.len() - 1];
+            let version = if insert {
+                snapshot.insert(x);
+                last.insert(x)
+            } else {
+                snapshot.remove(&x);
+                last.remove(&x)
+            };
+            versions.push(version);
+            snapshots.push(snapshot);
+        }
+        versions.iter().zip(snapshots).all(|(version, snapshot)| {
+            version.validate()
+                && version.len() == snapshot.len()
+                && version.iter().eq(snapshot.iter())
+        })
+    }
+    quickcheck(matches_snapshots as fn(Vec<(bool, i8)>) -> bool);
+}
@@ -1,4 +1,11 @@
+// The upstream tests are written in a style clippy flags; allow only the lints they trip
+#![allow(
+    clippy::empty_line_after_doc_comments,
+    clippy::needless_return,
+    clippy::unnecessary_map_or,
+)]
 use links::tree::*;
+use links::{avl::AvlTree, rbtree::RedBlackTree, sorted_set::SortedSet, treap::Treap};
 use quickcheck::quickcheck;
 #[allow(unused_imports)]
 use std::collections::HashSet;
@@ -30,6 +37,7 @@ fn validate<T: Ord>(node: &TreeNode<T>) -> bool {
 
 /// A wrapper over i32 that implements `PartialOrd`, `PartialEq`, `Eq`, `Ord`
 #[derive(PartialOrd, PartialEq, Eq, Ord)]
+#[allow(dead_code)]
 struct Num(i32);
 
 /// This test checks that the default implementation of `TreeNode` is `Leaf`
@@ -248,10 +256,22 @@ fn test_rebalance_root_5() {
 
 /// This test uses "property-based testing": this means it generates random test cases
 /// and checks that a property holds for all of them.
-/// In this case, the property is that the tree is always valid after inserting a value.
+/// In this case, the property is that the tree is always valid after inserting a value, for
+/// every `SortedSet` backend.
 #[test]
 fn test_insert_5() {
-    fn insert_is_valid(v: Vec<i32>) -> bool {
+    fn insert_is_valid<S: SortedSet<i32>>(v: Vec<i32>) -> bool {
+        let mut t = S::new();
+        for x in v.iter() {
+            t.insert(*x);
+            if !t.validate() || !t.contains(x) {
+                return false;
+            }
+        }
+        return true;
+    }
+    // `validate` above checks `TreeNode` independently of the crate's own `validate`
+    fn tree_node_is_valid(v: Vec<i32>) -> bool {
         let mut t = TreeNode::new();
         for x in v.iter() {
             t.insert(*x);
@@ -261,7 +281,11 @@ fn test_insert_5() {
         }
         return true;
     }
-    quickcheck(insert_is_valid as fn(Vec<i32>) -> bool);
+    quickcheck(insert_is_valid::<TreeNode<i32>> as fn(Vec<i32>) -> bool);
+    quickcheck(insert_is_valid::<AvlTree<i32>> as fn(Vec<i32>) -> bool);
+    quickcheck(insert_is_valid::<RedBlackTree<i32>> as fn(Vec<i32>) -> bool);
+    quickcheck(insert_is_valid::<Treap<i32>> as fn(Vec<i32>) -> bool);
+    quickcheck(tree_node_is_valid as fn(Vec<i32>) -> bool);
 }
 
 /// This test uses "property-based testing": this means it generates random test cases
@@ -297,4 +321,547 @@ fn test_insert_roundtrip_10() {
 }
 */
 
+
+/// This test checks that removing values keeps the tree balanced and returns the removed value
+#[test]
+fn test_remove_simple_5() {
+    let mut t = TreeNode::new();
+    for x in 1..=7 {
+        t.insert(x);
+    }
+    assert_eq!(t.remove(&4), Some(4));
+    assert!(validate(&t));
+    assert_eq!(t.remove(&4), None);
+    assert_eq!(t.pop_min(), Some(1));
+    assert_eq!(t.pop_max(), Some(7));
+    assert_eq!(t.remove(&2), Some(2));
+    assert_eq!(t.remove(&3), Some(3));
+    assert!(validate(&t));
+    let v: Vec<i32> = t.into();
+    assert_eq!(v, vec![5, 6]);
+
+    let mut empty: TreeNode<i32> = TreeNode::new();
+    assert_eq!(empty.pop_min(), None);
+    assert_eq!(empty.pop_max(), None);
+    assert_eq!(empty.remove(&0), None);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that the tree stays valid and holds the same values as a
+/// `BTreeSet` under any mix of inserts, removes, `pop_min` and `pop_max`, for every `SortedSet`
+/// backend. Backends other than `TreeNode` pop their extremes by removing the first or last value
+/// of their iterator.
+#[test]
+fn test_insert_remove_10() {
+    fn matches_btreeset(ops: Vec<(u8, i8)>) -> bool {
+        let mut t = TreeNode::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for (op, x) in ops {
+            let ok = match op % 4 {
+                0 => {
+                    t.insert(x);
+                    expected.insert(x);
+                    true
+                }
+                1 => t.remove(&x) == expected.take(&x),
+                2 => t.pop_min() == expected.pop_first(),
+                _ => t.pop_max() == expected.pop_last(),
+            };
+            if !ok || !validate(&t) {
+                return false;
+            }
+        }
+        let v: Vec<i8> = t.into();
+        v == expected.into_iter().collect::<Vec<_>>()
+    }
+    fn backend_matches_btreeset<S: SortedSet<i8>>(ops: Vec<(u8, i8)>) -> bool {
+        let mut t = S::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for (op, x) in ops {
+            let ok = match op % 4 {
+                0 => {
+                    t.insert(x);
+                    expected.insert(x);
+                    true
+                }
+                1 => t.contains(&x) == expected.contains(&x) && t.remove(&x) == expected.take(&x),
+                2 => {
+                    let min = t.iter().next().copied();
+                    min.and_then(|min| t.remove(&min)) == expected.pop_first()
+                }
+                _ => {
+                    let max = t.iter().last().copied();
+                    max.and_then(|max| t.remove(&max)) == expected.pop_last()
+                }
+            };
+            if !ok || !t.validate() {
+                return false;
+            }
+        }
+        t.iter().eq(expected.iter())
+    }
+    quickcheck(matches_btreeset as fn(Vec<(u8, i8)>) -> bool);
+    quickcheck(backend_matches_btreeset::<TreeNode<i8>> as fn(Vec<(u8, i8)>) -> bool);
+    quickcheck(backend_matches_btreeset::<AvlTree<i8>> as fn(Vec<(u8, i8)>) -> bool);
+    quickcheck(backend_matches_btreeset::<RedBlackTree<i8>> as fn(Vec<(u8, i8)>) -> bool);
+    quickcheck(backend_matches_btreeset::<Treap<i8>> as fn(Vec<(u8, i8)>) -> bool);
+}
+
+
+/// This test checks that lookups accept a borrowed form of the value type
+#[test]
+fn test_lookup_borrowed_3() {
+    let mut t: TreeNode<String> = TreeNode::new();
+    for word in ["pear", "apple", "fig", "kiwi"] {
+        t.insert(word.to_string());
+    }
+    assert!(t.contains("fig"));
+    assert!(!t.contains("plum"));
+    assert_eq!(t.get("kiwi").map(String::as_str), Some("kiwi"));
+    assert_eq!(t.min().map(String::as_str), Some("apple"));
+    assert_eq!(t.max().map(String::as_str), Some("pear"));
+    assert_eq!(t.floor("grape").map(String::as_str), Some("fig"));
+    assert_eq!(t.ceiling("grape").map(String::as_str), Some("kiwi"));
+    assert_eq!(t.predecessor("fig").map(String::as_str), Some("apple"));
+    assert_eq!(t.successor("fig").map(String::as_str), Some("kiwi"));
+    assert_eq!(t.successor("pear"), None);
+    assert_eq!(TreeNode::<String>::new().min(), None);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that every lookup agrees with the equivalent query on a
+/// `BTreeSet`.
+#[test]
+fn test_lookup_10() {
+    use std::ops::Bound::{Excluded, Unbounded};
+
+    fn matches_btreeset(v: Vec<i8>, queries: Vec<i8>) -> bool {
+        let mut t = TreeNode::new();
+        for x in v.iter() {
+            t.insert(*x);
+        }
+        let set: std::collections::BTreeSet<i8> = v.into_iter().collect();
+        t.min() == set.first()
+            && t.max() == set.last()
+            && queries.iter().all(|q| {
+                t.contains(q) == set.contains(q)
+                    && t.get(q) == set.get(q)
+                    && t.floor(q) == set.range(..=q).next_back()
+                    && t.ceiling(q) == set.range(q..).next()
+                    && t.predecessor(q) == set.range(..q).next_back()
+                    && t.successor(q) == set.range((Excluded(q), Unbounded)).next()
+            })
+    }
+    quickcheck(matches_btreeset as fn(Vec<i8>, Vec<i8>) -> bool);
+}
+
+
+/// Builds a range bound from a generated tag and value, for the range tests below
+fn bound(tag: u8, value: i8) -> std::ops::Bound<i8> {
+    match tag % 3 {
+        0 => std::ops::Bound::Included(value),
+        1 => std::ops::Bound::Excluded(value),
+        _ => std::ops::Bound::Unbounded,
+    }
+}
+
+/// Returns the values of `set` in `range`, or nothing if `BTreeSet::range` would reject the range
+fn btreeset_range(
+    set: &std::collections::BTreeSet<i8>,
+    range: (std::ops::Bound<i8>, std::ops::Bound<i8>),
+) -> Vec<i8> {
+    use std::ops::Bound::*;
+    let valid = match range {
+        (Included(a), Included(b)) => a <= b,
+        (Included(a) | Excluded(a), Included(b) | Excluded(b)) => a < b,
+        _ => true,
+    };
+    if valid {
+        set.range(range).copied().collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// This test checks range queries on a small tree
+#[test]
+fn test_range_simple_5() {
+    use std::ops::Bound::{Excluded, Included};
+    let mut t = TreeNode::new();
+    for x in 0..10 {
+        t.insert(x);
+    }
+    assert_eq!(t.range(3..6).copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    assert_eq!(t.range(..=2).rev().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+    assert_eq!(t.range(8..).count(), 2);
+    assert_eq!(t.range((Included(6), Excluded(3))).next(), None);
+    assert_eq!(t.range_count(2..=7), 6);
+
+    let mut r = t.range(2..5);
+    assert_eq!(r.next(), Some(&2));
+    assert_eq!(r.next_back(), Some(&4));
+    assert_eq!(r.next(), Some(&3));
+    assert_eq!(r.next_back(), None);
+    assert_eq!(r.next(), None);
+
+    assert_eq!(t.remove_range(2..8), vec![2, 3, 4, 5, 6, 7]);
+    assert!(validate(&t));
+    let v: Vec<i32> = t.into();
+    assert_eq!(v, vec![0, 1, 8, 9]);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that `range`, iterated from either or both ends, `range_count`
+/// and `remove_range` agree with `BTreeSet::range`.
+#[test]
+fn test_range_10() {
+    fn matches_btreeset(v: Vec<i8>, lo: (u8, i8), hi: (u8, i8), ends: Vec<bool>) -> bool {
+        let mut t = TreeNode::new();
+        for x in v.iter() {
+            t.insert(*x);
+        }
+        let set: std::collections::BTreeSet<i8> = v.into_iter().collect();
+        let range = (bound(lo.0, lo.1), bound(hi.0, hi.1));
+        let expected = btreeset_range(&set, range);
+
+        let forward: Vec<i8> = t.range(range).copied().collect();
+        let backward: Vec<i8> = t.range(range).rev().copied().collect();
+
+        // Take values alternately from either end, as directed by `ends`
+        let mut iter = t.range(range);
+        let (mut from_front, mut from_back) = (Vec::new(), Vec::new());
+        for from_back_end in ends.into_iter().chain(std::iter::repeat(false)) {
+            let next = if from_back_end {
+                iter.next_back().map(|x| from_back.push(*x))
+            } else {
+                iter.next().map(|x| from_front.push(*x))
+            };
+            if next.is_none() {
+                break;
+            }
+        }
+        from_front.extend(from_back.into_iter().rev());
+
+        let count = t.range_count(range);
+        let removed = t.remove_range(range);
+        let rest: Vec<i8> = t.clone().into();
+        forward == expected
+            && backward.into_iter().rev().eq(expected.iter().copied())
+            && from_front == expected
+            && count == expected.len()
+            && removed == expected
+            && validate(&t)
+            && rest == set.iter().copied().filter(|x| !expected.contains(x)).collect::<Vec<_>>()
+    }
+    quickcheck(matches_btreeset as fn(Vec<i8>, (u8, i8), (u8, i8), Vec<bool>) -> bool);
+}
+
+
+/// This test checks every traversal order on a fixed tree
+#[test]
+fn test_traversals_simple_5() {
+    //       4
+    //     /   \
+    //    2     6
+    //   / \     \
+    //  1   3     7
+    let t = TreeNode::node(
+        4,
+        TreeNode::node(
+            2,
+            TreeNode::node(1, TreeNode::Leaf, TreeNode::Leaf),
+            TreeNode::node(3, TreeNode::Leaf, TreeNode::Leaf),
+        ),
+        TreeNode::node(
+            6,
+            TreeNode::Leaf,
+            TreeNode::node(7, TreeNode::Leaf, TreeNode::Leaf),
+        ),
+    );
+    assert_eq!(t.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 6, 7]);
+    assert_eq!(t.iter().rev().copied().collect::<Vec<_>>(), vec![7, 6, 4, 3, 2, 1]);
+    assert_eq!(t.preorder().copied().collect::<Vec<_>>(), vec![4, 2, 1, 3, 6, 7]);
+    assert_eq!(t.postorder().copied().collect::<Vec<_>>(), vec![1, 3, 2, 7, 6, 4]);
+    assert_eq!(t.level_order().copied().collect::<Vec<_>>(), vec![4, 2, 6, 1, 3, 7]);
+    assert_eq!((&t).into_iter().count(), 6);
+    assert_eq!(t.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 6, 7]);
+
+    let empty: TreeNode<i32> = TreeNode::new();
+    assert_eq!(empty.iter().next(), None);
+    assert_eq!(empty.preorder().next(), None);
+    assert_eq!(empty.postorder().next(), None);
+    assert_eq!(empty.level_order().next(), None);
+    assert_eq!(empty.into_iter().next(), None);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that each traversal iterator yields the same values as a
+/// recursive traversal in the same order.
+#[test]
+fn test_traversals_10() {
+    fn pre(t: &TreeNode<i32>, out: &mut Vec<i32>) {
+        if let TreeNode::Node(v, l, r) = t {
+            out.push(*v);
+            pre(l, out);
+            pre(r, out);
+        }
+    }
+    fn post(t: &TreeNode<i32>, out: &mut Vec<i32>) {
+        if let TreeNode::Node(v, l, r) = t {
+            post(l, out);
+            post(r, out);
+            out.push(*v);
+        }
+    }
+    fn level(t: &TreeNode<i32>, depth: usize, levels: &mut Vec<Vec<i32>>) {
+        if let TreeNode::Node(v, l, r) = t {
+            if levels.len() == depth {
+                levels.push(Vec::new());
+            }
+            levels[depth].push(*v);
+            level(l, depth + 1, levels);
+            level(r, depth + 1, levels);
+        }
+    }
+    fn matches_recursive(v: Vec<i32>, ends: Vec<bool>) -> bool {
+        let mut t = TreeNode::new();
+        for x in v.iter() {
+            t.insert(*x);
+        }
+        let (mut expected_pre, mut expected_post, mut levels) = (Vec::new(), Vec::new(), Vec::new());
+        pre(&t, &mut expected_pre);
+        post(&t, &mut expected_post);
+        level(&t, 0, &mut levels);
+        let expected_level: Vec<i32> = levels.concat();
+        let mut sorted = v;
+        sorted.sort();
+        sorted.dedup();
+
+        // Take values alternately from either end, as directed by `ends`
+        let mut iter = t.iter();
+        let (mut from_front, mut from_back) = (Vec::new(), Vec::new());
+        for from_back_end in ends.into_iter().chain(std::iter::repeat(false)) {
+            let next = if from_back_end {
+                iter.next_back().map(|x| from_back.push(*x))
+            } else {
+                iter.next().map(|x| from_front.push(*x))
+            };
+            if next.is_none() {
+                break;
+            }
+        }
+        from_front.extend(from_back.into_iter().rev());
+
+        t.preorder().copied().eq(expected_pre)
+            && t.postorder().copied().eq(expected_post)
+            && t.level_order().copied().eq(expected_level)
+            && from_front == sorted
+            && t.into_iter().eq(sorted)
+    }
+    quickcheck(matches_recursive as fn(Vec<i32>, Vec<bool>) -> bool);
+}
+
+
+/// This test checks the basic map operations and the entry API
+#[test]
+fn test_map_simple_5() {
+    let mut m = AvlMap::new();
+    assert!(m.is_empty());
+    assert_eq!(m.insert("b", 2), None);
+    assert_eq!(m.insert("a", 1), None);
+    assert_eq!(m.insert("c", 3), None);
+    assert_eq!(m.insert("b", 20), Some(2));
+    assert_eq!(m.len(), 3);
+    assert_eq!(m.get("b"), Some(&20));
+    assert_eq!(m.get("z"), None);
+    *m.get_mut("a").unwrap() += 10;
+    assert!(m.contains_key("a"));
+
+    *m.entry("d").or_insert(0) += 4;
+    m.entry("a").and_modify(|v| *v += 100).or_insert(0);
+    m.entry("e").and_modify(|v| *v += 100).or_insert(5);
+    assert_eq!(*m.entry("c").or_default(), 3);
+    assert_eq!(m.entry("f").key(), &"f");
+
+    assert_eq!(m.keys().copied().collect::<Vec<_>>(), vec!["a", "b", "c", "d", "e"]);
+    assert_eq!(m.values().copied().collect::<Vec<_>>(), vec![111, 20, 3, 4, 5]);
+    assert_eq!(m.remove("b"), Some(20));
+    assert_eq!(m.remove("b"), None);
+    assert_eq!(
+        m.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        vec![("a", 111), ("c", 3), ("d", 4), ("e", 5)]
+    );
+    assert!(m.validate());
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that a random sequence of inserts, removals and entry updates
+/// leaves an `AvlMap` balanced and holding the same entries as a `BTreeMap`.
+#[test]
+fn test_map_10() {
+    fn matches_btreemap(ops: Vec<(u8, i8, i32)>) -> bool {
+        let mut m = AvlMap::new();
+        let mut expected = std::collections::BTreeMap::new();
+        for (op, key, value) in ops {
+            let agrees = match op % 4 {
+                0 => m.insert(key, value) == expected.insert(key, value),
+                1 => m.remove(&key) == expected.remove(&key),
+                2 => {
+                    let a = *m.entry(key).and_modify(|v| *v ^= value).or_insert(value);
+                    let b = *expected.entry(key).and_modify(|v| *v ^= value).or_insert(value);
+                    a == b
+                }
+                _ => m.get(&key) == expected.get(&key) && m.get_mut(&key) == expected.get_mut(&key),
+            };
+            if !agrees || m.len() != expected.len() || !m.validate() {
+                return false;
+            }
+        }
+        m.iter().eq(expected.iter())
+            && m.keys().eq(expected.keys())
+            && m.values().eq(expected.values())
+    }
+    quickcheck(matches_btreemap as fn(Vec<(u8, i8, i32)>) -> bool);
+}
+
+
+/// This test checks that sorted input is built into a perfectly balanced tree
+#[test]
+fn test_from_sorted_simple_5() {
+    let t = TreeNode::from_sorted(1..=7);
+    let expected = TreeNode::node(
+        4,
+        TreeNode::node(
+            2,
+            TreeNode::node(1, TreeNode::Leaf, TreeNode::Leaf),
+            TreeNode::node(3, TreeNode::Leaf, TreeNode::Leaf),
+        ),
+        TreeNode::node(
+            6,
+            TreeNode::node(5, TreeNode::Leaf, TreeNode::Leaf),
+            TreeNode::node(7, TreeNode::Leaf, TreeNode::Leaf),
+        ),
+    );
+    assert!(t == expected);
+    assert!(TreeNode::from_sorted_dedup(vec![1, 1, 2, 3, 3, 3, 4, 5, 6, 7, 7]) == expected);
+    assert!(TreeNode::from(vec![1, 2, 3, 4, 5, 6, 7]) == expected);
+    assert!(TreeNode::<i32>::from_sorted(Vec::new()) == TreeNode::Leaf);
+
+    // Unsorted input still gives a valid tree through `FromIterator`
+    let t: TreeNode<i32> = vec![3, 1, 2].into();
+    assert!(validate(&t));
+    let t: TreeNode<i32> = vec![5, 4, 6].into_iter().collect();
+    assert_eq!(t.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+}
+
+/// This test checks that `from_sorted` rejects input that is not strictly ascending
+#[test]
+#[should_panic(expected = "strictly ascending")]
+fn test_from_sorted_unsorted_1() {
+    TreeNode::from_sorted(vec![1, 3, 2]);
+}
+
+/// This test checks that converting a large sorted vector takes the linear path; inserting the
+/// values one by one would take far too long
+#[test]
+fn test_from_sorted_large_3() {
+    let n = 1_000_000;
+    let t: TreeNode<u32> = (0..n).collect::<Vec<_>>().into();
+    assert!(validate(&t));
+    assert_eq!(t.min(), Some(&0));
+    assert_eq!(t.max(), Some(&(n - 1)));
+    // 2^19 < n + 1 <= 2^20
+    assert_eq!(t.height(), 20);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that trees built from sorted input are balanced search trees of
+/// minimal height holding exactly the input values.
+#[test]
+fn test_from_sorted_10() {
+    fn balanced_and_complete(mut v: Vec<i32>) -> bool {
+        v.sort();
+        let with_duplicates = TreeNode::from_sorted_dedup(v.clone());
+        let converted = TreeNode::from(v.clone());
+        v.dedup();
+        let t = TreeNode::from_sorted(v.clone());
+        let min_height = (usize::BITS - v.len().leading_zeros()) as usize;
+        validate(&t)
+            && t.height() == min_height
+            && t.iter().eq(v.iter())
+            && t == with_duplicates
+            && t == converted
+    }
+    quickcheck(balanced_and_complete as fn(Vec<i32>) -> bool);
+}
+
+
+/// This test checks `split` and `join` on a small tree
+#[test]
+fn test_split_join_simple_5() {
+    let t = TreeNode::from_sorted(0..10);
+    let (left, found, right) = t.split(&4);
+    assert_eq!(found, Some(4));
+    assert!(validate(&left) && validate(&right));
+    assert_eq!(left.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    assert_eq!(right.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7, 8, 9]);
+
+    let t = TreeNode::join(left, 4, right);
+    assert!(validate(&t));
+    assert!(t.iter().copied().eq(0..10));
+
+    let (left, found, right) = t.split(&20);
+    assert_eq!(found, None);
+    assert_eq!(right, TreeNode::Leaf);
+    let t = TreeNode::join(left, 10, TreeNode::from_sorted(11..12));
+    assert!(validate(&t));
+}
+
+/// This test checks that `join` rejects values that are out of order
+#[test]
+#[should_panic(expected = "ascending order")]
+fn test_join_unordered_1() {
+    TreeNode::join(TreeNode::from_sorted(0..5), 3, TreeNode::Leaf);
+}
+
+/// This test uses "property-based testing": this means it generates random test cases
+/// and checks that a property holds for all of them.
+/// In this case, the property is that `split`, `join` and the set operations built on them agree
+/// with `BTreeSet` and always produce balanced search trees.
+#[test]
+fn test_set_algebra_10() {
+    use std::collections::BTreeSet;
+    fn matches_btreeset(a: Vec<i8>, b: Vec<i8>, pivot: i8) -> bool {
+        let (ta, tb): (TreeNode<i8>, TreeNode<i8>) = (a.iter().copied().collect(), b.into());
+        let (sa, sb): (BTreeSet<i8>, BTreeSet<i8>) =
+            (ta.iter().copied().collect(), tb.iter().copied().collect());
+        let same = |t: &TreeNode<i8>, s: BTreeSet<i8>| validate(t) && t.iter().eq(s.iter());
+
+        let (left, found, right) = ta.clone().split(&pivot);
+        let split_ok = same(&left, sa.range(..pivot).copied().collect())
+            && same(&right, sa.range(pivot..).filter(|x| **x != pivot).copied().collect())
+            && found == sa.get(&pivot).copied();
+        let joined = TreeNode::join(left, pivot, right);
+        let mut with_pivot = sa.clone();
+        with_pivot.insert(pivot);
+
+        split_ok
+            && same(&joined, with_pivot)
+            && same(&ta.clone().union(tb.clone()), &sa | &sb)
+            && same(&ta.clone().intersection(tb.clone()), &sa & &sb)
+            && same(&ta.clone().difference(tb.clone()), &sa - &sb)
+            && same(&ta.clone().symmetric_difference(tb.clone()), &sa ^ &sb)
+            && ta.clone().is_subset(tb.clone()) == sa.is_subset(&sb)
+            && ta.clone().is_disjoint(tb.clone()) == sa.is_disjoint(&sb)
+            && ta.clone().is_subset(ta.union(tb))
+    }
+    quickcheck(matches_btreeset as fn(Vec<i8>, Vec<i8>, i8) -> bool);
+}
+
 const _UNUSED: bool = true;